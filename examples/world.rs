@@ -5,7 +5,7 @@ use raytracer::{
     body::{sphere::Sphere, Body},
     camera::Camera,
     color::Color,
-    light::{Light, PointLight},
+    light::PointLight,
     material::Material,
     matrix::Matrix,
     point,
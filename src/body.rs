@@ -1,27 +1,56 @@
 use uuid::Uuid;
 
 use crate::{
+    bounds::BoundingBox,
     material::Material,
     matrix::Matrix,
     ray::{intersection::Intersection, Ray},
     tuple::Tuple,
 };
 
+pub mod cone;
+pub mod csg;
+pub mod cube;
+pub mod cylinder;
+pub mod group;
 pub mod plane;
+pub mod smooth_triangle;
 pub mod sphere;
+pub mod triangle;
 
-pub trait Body {
+/// Bodies are shared across render threads, hence `Send + Sync`.
+pub trait Body: Send + Sync {
     fn new() -> Self
     where
         Self: Sized;
-    fn intersect(&self, ray: &Ray) -> Option<[Intersection; 2]>; // TODO: change to array slice
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>>;
     fn transform(&self, by: Matrix<4, 4>) -> Self
     where
         Self: Sized;
     fn normal_at(&self, point: Tuple) -> Tuple;
-    // TODO: ID useful for anything other than testing?
+    /// Normal at `point` for a concrete `intersection`, for bodies that need more than the
+    /// point itself (e.g. interpolating normals from `Intersection::get_uv`).
+    fn normal_at_intersection(&self, point: Tuple, _intersection: &Intersection) -> Tuple {
+        self.normal_at(point)
+    }
     fn get_id(&self) -> Uuid;
+    /// Checks whether `body` is this very body or contained in it. Compares references rather
+    /// than ids, as transformed copies of a body share its id.
+    fn includes(&self, body: &dyn Body) -> bool {
+        std::ptr::addr_eq(self, body)
+    }
     fn get_transformation(&self) -> Matrix<4, 4>;
+    /// Inverse of the transformation, cached whenever the transformation changes.
+    fn get_inverse(&self) -> Matrix<4, 4>;
+    /// Transposed inverse of the transformation, used to transform normals.
+    fn get_inverse_transpose(&self) -> Matrix<4, 4>;
+    /// Axis-aligned bounds of the body in object space.
+    fn get_bounds(&self) -> BoundingBox;
+    /// Axis-aligned bounds of the body after applying its transformation, i.e. in the space of
+    /// the containing group or world.
+    fn get_parent_space_bounds(&self) -> BoundingBox {
+        self.get_bounds().transform(self.get_transformation())
+    }
     fn get_material(&self) -> Material;
     fn set_material(&self, material: Material) -> Self
     where
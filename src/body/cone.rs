@@ -0,0 +1,333 @@
+use uuid::Uuid;
+
+use crate::{
+    bounds::BoundingBox,
+    material::Material,
+    matrix::Matrix,
+    point,
+    ray::{intersection::Intersection, Ray},
+    tuple::{Position, Tuple},
+    utils::EPSILON,
+};
+
+use super::Body;
+
+/// Double-napped cone around the y axis in object space, with its tip at the origin.
+///
+/// The radius at any height `y` equals `|y|`. Infinite by default; truncated by `minimum`
+/// and `maximum` (both exclusive) and capped at both ends when `closed`.
+#[derive(Clone, Debug)]
+pub struct Cone {
+    id: Uuid,
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    inverse_transpose: Matrix<4, 4>,
+    material: Material,
+    minimum: f64,
+    maximum: f64,
+    closed: bool,
+}
+
+impl Body for Cone {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            id: Uuid::new_v4(),
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            inverse_transpose: Matrix::identity_matrix(),
+            material: Material::default(),
+            minimum: f64::NEG_INFINITY,
+            maximum: f64::INFINITY,
+            closed: false,
+        }
+    }
+
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let ray = ray.transform(self.inverse);
+        let origin = ray.get_origin();
+        let direction = ray.get_direction();
+        let mut intersections = vec![];
+
+        let (a, b, c) = Self::coefficients(&ray);
+
+        if a.abs() < EPSILON {
+            // ray parallel to one of the halves hits the other one exactly once
+            if b.abs() >= EPSILON {
+                let t = -c / (2f64 * b);
+                let y = origin[Position::Y] + t * direction[Position::Y];
+                if self.minimum < y && y < self.maximum {
+                    intersections.push(Intersection::new(t, self));
+                }
+            }
+        } else {
+            let discriminant = Self::discriminant(a, b, c);
+
+            if discriminant < 0f64 {
+                return intersections;
+            }
+
+            let mut t0 = (-b - discriminant.sqrt()) / (2f64 * a);
+            let mut t1 = (-b + discriminant.sqrt()) / (2f64 * a);
+            if t0 > t1 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+
+            for t in [t0, t1] {
+                let y = origin[Position::Y] + t * direction[Position::Y];
+                if self.minimum < y && y < self.maximum {
+                    intersections.push(Intersection::new(t, self));
+                }
+            }
+        }
+
+        self.intersect_caps(&ray, &mut intersections);
+        intersections
+    }
+
+    fn transform(&self, by: Matrix<4, 4>) -> Self
+    where
+        Self: Sized,
+    {
+        let transformation = self.transformation * by;
+        let inverse = transformation.inverse();
+        Self {
+            transformation,
+            inverse,
+            inverse_transpose: inverse.transpose(),
+            ..self.clone()
+        }
+    }
+
+    fn normal_at(&self, point: Tuple) -> Tuple {
+        // convert to object space
+        let point = self.inverse * point;
+
+        let x = point[Position::X];
+        let y = point[Position::Y];
+        let z = point[Position::Z];
+        let distance = x.powi(2) + z.powi(2);
+
+        let object_normal = if distance < y.powi(2) && y >= self.maximum - EPSILON {
+            Tuple::new_vec(0.0, 1.0, 0.0)
+        } else if distance < y.powi(2) && y <= self.minimum + EPSILON {
+            Tuple::new_vec(0.0, -1.0, 0.0)
+        } else {
+            let normal_y = if y > 0f64 {
+                -distance.sqrt()
+            } else {
+                distance.sqrt()
+            };
+            Tuple::new_vec(x, normal_y, z)
+        };
+
+        let world_normal = self.inverse_transpose * object_normal;
+        // WORKAROUND! page 82
+        Tuple::new_vec(
+            world_normal[Position::X],
+            world_normal[Position::Y],
+            world_normal[Position::Z],
+        )
+        .normalize()
+    }
+
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+
+    fn get_inverse_transpose(&self) -> Matrix<4, 4> {
+        self.inverse_transpose
+    }
+
+    fn get_bounds(&self) -> BoundingBox {
+        let limit = self.minimum.abs().max(self.maximum.abs());
+        BoundingBox::new(
+            point!(-limit, self.minimum, -limit),
+            point!(limit, self.maximum, limit),
+        )
+    }
+
+    fn get_material(&self) -> Material {
+        self.material.clone()
+    }
+
+    fn set_material(&self, material: Material) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            material,
+            ..self.clone()
+        }
+    }
+}
+
+impl Cone {
+    pub fn set_minimum(&self, minimum: f64) -> Self {
+        Self {
+            minimum,
+            ..self.clone()
+        }
+    }
+
+    pub fn set_maximum(&self, maximum: f64) -> Self {
+        Self {
+            maximum,
+            ..self.clone()
+        }
+    }
+
+    pub fn set_closed(&self, closed: bool) -> Self {
+        Self {
+            closed,
+            ..self.clone()
+        }
+    }
+
+    pub fn get_minimum(&self) -> f64 {
+        self.minimum
+    }
+
+    pub fn get_maximum(&self) -> f64 {
+        self.maximum
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Coefficients `(a, b, c)` of the quadratic equation of the ray against the cone.
+    fn coefficients(ray: &Ray) -> (f64, f64, f64) {
+        let origin = ray.get_origin();
+        let direction = ray.get_direction();
+
+        let a = direction[Position::X].powi(2) - direction[Position::Y].powi(2)
+            + direction[Position::Z].powi(2);
+        let b = 2f64 * origin[Position::X] * direction[Position::X]
+            - 2f64 * origin[Position::Y] * direction[Position::Y]
+            + 2f64 * origin[Position::Z] * direction[Position::Z];
+        let c =
+            origin[Position::X].powi(2) - origin[Position::Y].powi(2) + origin[Position::Z].powi(2);
+
+        (a, b, c)
+    }
+
+    fn discriminant(a: f64, b: f64, c: f64) -> f64 {
+        b.powf(2f64) - 4f64 * a * c
+    }
+
+    /// Checks whether the intersection at `t` lies within `radius` of the y axis.
+    fn check_cap(ray: &Ray, t: f64, radius: f64) -> bool {
+        let x = ray.get_origin()[Position::X] + t * ray.get_direction()[Position::X];
+        let z = ray.get_origin()[Position::Z] + t * ray.get_direction()[Position::Z];
+        x.powi(2) + z.powi(2) <= radius.powi(2)
+    }
+
+    fn intersect_caps<'a>(&'a self, ray: &Ray, intersections: &mut Vec<Intersection<'a>>) {
+        // caps only matter if the cone is closed and could be intersected
+        if !self.closed || ray.get_direction()[Position::Y].abs() < EPSILON {
+            return;
+        }
+
+        for y in [self.minimum, self.maximum] {
+            let t = (y - ray.get_origin()[Position::Y]) / ray.get_direction()[Position::Y];
+            if Self::check_cap(ray, t, y.abs()) {
+                intersections.push(Intersection::new(t, self));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{body::Body, point, ray::Ray, tuple::Tuple, utils::assert_f64_eq, vector};
+
+    use super::Cone;
+
+    #[test]
+    fn intersecting_cone_with_ray() {
+        let shape = Cone::new();
+        let cases = [
+            (point!(0, 0, -5), vector!(0, 0, 1), 5.0, 5.0),
+            (point!(0, 0, -5), vector!(1, 1, 1), 8.66025, 8.66025),
+            (point!(1, 1, -5), vector!(-0.5, -1, 1), 4.55006, 49.44994),
+        ];
+
+        for (origin, direction, t0, t1) in cases {
+            let r = Ray::new(origin, direction.normalize());
+            let xs = shape.intersect(&r);
+            assert_eq!(xs.len(), 2);
+            assert_f64_eq!(xs[0].get_t(), t0);
+            assert_f64_eq!(xs[1].get_t(), t1)
+        }
+    }
+
+    #[test]
+    fn intersecting_cone_with_ray_parallel_to_one_half() {
+        let shape = Cone::new();
+        let r = Ray::new(point!(0, 0, -1), vector!(0, 1, 1).normalize());
+        let xs = shape.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_f64_eq!(xs[0].get_t(), 0.35355)
+    }
+
+    #[test]
+    fn intersecting_cone_end_caps() {
+        let shape = Cone::new()
+            .set_minimum(-0.5)
+            .set_maximum(0.5)
+            .set_closed(true);
+        let cases = [
+            (point!(0, 0, -5), vector!(0, 1, 0), 0),
+            (point!(0, 0, -0.25), vector!(0, 1, 1), 2),
+            (point!(0, 0, -0.25), vector!(0, 1, 0), 4),
+        ];
+
+        for (origin, direction, count) in cases {
+            let r = Ray::new(origin, direction.normalize());
+            assert_eq!(shape.intersect(&r).len(), count)
+        }
+    }
+
+    #[test]
+    fn normal_on_cone() {
+        let shape = Cone::new();
+        let cases = [
+            (point!(1, 1, 1), vector!(1, -2f64.sqrt(), 1)),
+            (point!(-1, -1, 0), vector!(-1, 1, 0)),
+        ];
+
+        for (point, normal) in cases {
+            assert_eq!(shape.normal_at(point), normal.normalize())
+        }
+    }
+
+    #[test]
+    fn normal_on_cone_end_caps() {
+        let shape = Cone::new()
+            .set_minimum(-1.0)
+            .set_maximum(2.0)
+            .set_closed(true);
+        assert_eq!(shape.normal_at(point!(0.5, 2, 0)), vector!(0, 1, 0));
+        assert_eq!(shape.normal_at(point!(0.5, -1, 0)), vector!(0, -1, 0))
+    }
+
+    #[test]
+    fn bounds_of_truncated_cone() {
+        let shape = Cone::new().set_minimum(-5.0).set_maximum(3.0);
+        let b = shape.get_bounds();
+        assert_eq!(b.get_min(), point!(-5, -5, -5));
+        assert_eq!(b.get_max(), point!(5, 3, 5));
+        assert!(!Cone::new().get_bounds().is_finite())
+    }
+}
@@ -0,0 +1,366 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    bounds::BoundingBox,
+    material::Material,
+    matrix::Matrix,
+    ray::{intersection::Intersection, Ray},
+    tuple::Tuple,
+};
+
+use super::{group::Group, Body};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+impl Operation {
+    /// Decides whether an intersection is part of the combined surface.
+    ///
+    /// `left_hit` tells which operand was hit, `inside_left` and `inside_right` whether the
+    /// intersection lies inside the left and right operand respectively.
+    pub fn intersection_allowed(
+        &self,
+        left_hit: bool,
+        inside_left: bool,
+        inside_right: bool,
+    ) -> bool {
+        match self {
+            Operation::Union => (left_hit && !inside_right) || (!left_hit && !inside_left),
+            Operation::Intersection => (left_hit && inside_right) || (!left_hit && inside_left),
+            Operation::Difference => (left_hit && !inside_right) || (!left_hit && inside_left),
+        }
+    }
+}
+
+/// Constructive solid geometry; combines two operands into a new body.
+///
+/// The operands keep their own transformations and materials, the transformation of the
+/// CSG body is applied on top of them like for a `Group`.
+#[derive(Clone)]
+pub struct Csg {
+    id: Uuid,
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    inverse_transpose: Matrix<4, 4>,
+    material: Material,
+    operation: Operation,
+    left: Arc<dyn Body>,
+    right: Arc<dyn Body>,
+}
+
+impl Body for Csg {
+    /// Creates the union of two empty groups.
+    /// Use `Csg::union`, `Csg::intersection` or `Csg::difference` to combine bodies.
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_operands(Operation::Union, Group::new(), Group::new())
+    }
+
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let ray = ray.transform(self.inverse);
+
+        let mut intersections: Vec<Intersection> = self
+            .left
+            .intersect(&ray)
+            .into_iter()
+            .chain(self.right.intersect(&ray))
+            .map(|intersection| intersection.with_parent(self.inverse))
+            .collect();
+
+        intersections.sort_by(|a, b| a.get_t().total_cmp(&b.get_t()));
+        self.filter_intersections(intersections)
+    }
+
+    fn transform(&self, by: Matrix<4, 4>) -> Self
+    where
+        Self: Sized,
+    {
+        let transformation = self.transformation * by;
+        let inverse = transformation.inverse();
+        Self {
+            transformation,
+            inverse,
+            inverse_transpose: inverse.transpose(),
+            ..self.clone()
+        }
+    }
+
+    fn normal_at(&self, _point: Tuple) -> Tuple {
+        panic!("CSG bodies have no surface, normals are computed on their operands.")
+    }
+
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn includes(&self, body: &dyn Body) -> bool {
+        std::ptr::addr_eq(self, body) || self.left.includes(body) || self.right.includes(body)
+    }
+
+    fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+
+    fn get_inverse_transpose(&self) -> Matrix<4, 4> {
+        self.inverse_transpose
+    }
+
+    fn get_bounds(&self) -> BoundingBox {
+        self.left
+            .get_parent_space_bounds()
+            .merge(&self.right.get_parent_space_bounds())
+    }
+
+    fn get_material(&self) -> Material {
+        self.material.clone()
+    }
+
+    fn set_material(&self, material: Material) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            material,
+            ..self.clone()
+        }
+    }
+}
+
+impl Csg {
+    pub fn from_operands<L: Body + 'static, R: Body + 'static>(
+        operation: Operation,
+        left: L,
+        right: R,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            inverse_transpose: Matrix::identity_matrix(),
+            material: Material::default(),
+            operation,
+            left: Arc::new(left),
+            right: Arc::new(right),
+        }
+    }
+
+    pub fn union<L: Body + 'static, R: Body + 'static>(left: L, right: R) -> Self {
+        Self::from_operands(Operation::Union, left, right)
+    }
+
+    pub fn intersection<L: Body + 'static, R: Body + 'static>(left: L, right: R) -> Self {
+        Self::from_operands(Operation::Intersection, left, right)
+    }
+
+    pub fn difference<L: Body + 'static, R: Body + 'static>(left: L, right: R) -> Self {
+        Self::from_operands(Operation::Difference, left, right)
+    }
+
+    pub fn get_operation(&self) -> Operation {
+        self.operation
+    }
+
+    pub fn get_left(&self) -> &dyn Body {
+        self.left.as_ref()
+    }
+
+    pub fn get_right(&self) -> &dyn Body {
+        self.right.as_ref()
+    }
+
+    /// Keeps the intersections on the surface of the combined body; expects them sorted.
+    fn filter_intersections<'a>(
+        &self,
+        intersections: Vec<Intersection<'a>>,
+    ) -> Vec<Intersection<'a>> {
+        // both operands start outside, every intersection toggles the respective state
+        let mut inside_left = false;
+        let mut inside_right = false;
+        let mut result = vec![];
+
+        for intersection in intersections {
+            let left_hit = self.left.includes(intersection.get_object());
+
+            if self
+                .operation
+                .intersection_allowed(left_hit, inside_left, inside_right)
+            {
+                result.push(intersection);
+            }
+
+            if left_hit {
+                inside_left = !inside_left;
+            } else {
+                inside_right = !inside_right;
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        body::{cube::Cube, sphere::Sphere, Body},
+        matrix::Matrix,
+        point,
+        ray::{intersection::Intersection, Ray},
+        tuple::Tuple,
+        vector,
+    };
+
+    use super::{Csg, Operation};
+
+    #[test]
+    fn csg_is_created_with_operation_and_two_bodies() {
+        let s1 = Sphere::new();
+        let s2 = Cube::new();
+        let c = Csg::union(s1.clone(), s2.clone());
+        assert_eq!(c.get_operation(), Operation::Union);
+        assert_eq!(c.get_left().get_id(), s1.get_id());
+        assert_eq!(c.get_right().get_id(), s2.get_id())
+    }
+
+    #[test]
+    fn evaluating_rule_for_csg_operation() {
+        let cases = [
+            (Operation::Union, true, true, true, false),
+            (Operation::Union, true, true, false, true),
+            (Operation::Union, true, false, true, false),
+            (Operation::Union, true, false, false, true),
+            (Operation::Union, false, true, true, false),
+            (Operation::Union, false, true, false, false),
+            (Operation::Union, false, false, true, true),
+            (Operation::Union, false, false, false, true),
+            (Operation::Intersection, true, true, true, true),
+            (Operation::Intersection, true, true, false, false),
+            (Operation::Intersection, true, false, true, true),
+            (Operation::Intersection, true, false, false, false),
+            (Operation::Intersection, false, true, true, true),
+            (Operation::Intersection, false, true, false, true),
+            (Operation::Intersection, false, false, true, false),
+            (Operation::Intersection, false, false, false, false),
+            (Operation::Difference, true, true, true, false),
+            (Operation::Difference, true, true, false, true),
+            (Operation::Difference, true, false, true, false),
+            (Operation::Difference, true, false, false, true),
+            (Operation::Difference, false, true, true, true),
+            (Operation::Difference, false, true, false, true),
+            (Operation::Difference, false, false, true, false),
+            (Operation::Difference, false, false, false, false),
+        ];
+
+        for (operation, left_hit, inside_left, inside_right, result) in cases {
+            assert_eq!(
+                operation.intersection_allowed(left_hit, inside_left, inside_right),
+                result
+            )
+        }
+    }
+
+    #[test]
+    fn filtering_list_of_intersections() {
+        let s1 = Sphere::new();
+        let s2 = Cube::new();
+        let cases = [
+            (Operation::Union, 0, 3),
+            (Operation::Intersection, 1, 2),
+            (Operation::Difference, 0, 1),
+        ];
+
+        for (operation, x0, x1) in cases {
+            let c = Csg::from_operands(operation, s1.clone(), s2.clone());
+            let xs = vec![
+                Intersection::new(1.0, c.get_left()),
+                Intersection::new(2.0, c.get_right()),
+                Intersection::new(3.0, c.get_left()),
+                Intersection::new(4.0, c.get_right()),
+            ];
+            let result = c.filter_intersections(xs.clone());
+            assert_eq!(result.len(), 2);
+            assert_eq!(result[0].get_t(), xs[x0].get_t());
+            assert_eq!(result[1].get_t(), xs[x1].get_t())
+        }
+    }
+
+    #[test]
+    fn operands_sharing_id_are_told_apart() {
+        let s = Sphere::new();
+        let c = Csg::difference(
+            s.clone(),
+            s.transform(Matrix::translation_matrix(0.0, 0.0, 0.5)),
+        );
+        assert_eq!(c.get_left().get_id(), c.get_right().get_id());
+
+        let r = Ray::new(point!(0, 0, -5), vector!(0, 0, 1));
+        let xs = c.intersect(&r);
+        assert_eq!(xs.len(), 2);
+        assert_eq!(xs[0].get_t(), 4.0);
+        assert_eq!(xs[1].get_t(), 4.5)
+    }
+
+    #[test]
+    fn ray_misses_csg_body() {
+        let c = Csg::union(Sphere::new(), Cube::new());
+        let r = Ray::new(point!(0, 2, -5), vector!(0, 0, 1));
+        assert!(c.intersect(&r).is_empty())
+    }
+
+    #[test]
+    fn ray_hits_csg_body() {
+        let s1 = Sphere::new();
+        let s2 = Sphere::new().transform(Matrix::translation_matrix(0.0, 0.0, 0.5));
+        let c = Csg::union(s1.clone(), s2.clone());
+        let r = Ray::new(point!(0, 0, -5), vector!(0, 0, 1));
+        let xs = c.intersect(&r);
+        assert_eq!(xs.len(), 2);
+        assert_eq!(xs[0].get_t(), 4.0);
+        assert_eq!(xs[0].get_object().get_id(), s1.get_id());
+        assert_eq!(xs[1].get_t(), 6.5);
+        assert_eq!(xs[1].get_object().get_id(), s2.get_id())
+    }
+
+    #[test]
+    fn difference_drills_hole() {
+        // elongated sphere drills a hole through the cube along the z axis
+        let cube = Cube::new();
+        let hole = Sphere::new().transform(Matrix::scaling_matrix(0.5, 0.5, 2.0));
+        let c = Csg::difference(cube, hole);
+
+        let through_hole = Ray::new(point!(0, 0, -5), vector!(0, 0, 1));
+        assert!(c.intersect(&through_hole).is_empty());
+
+        let beside_hole = Ray::new(point!(0.75, 0, -5), vector!(0, 0, 1));
+        assert_eq!(c.intersect(&beside_hole).len(), 2)
+    }
+
+    #[test]
+    fn transformed_csg_body_computes_normals_of_operands() {
+        let s = Sphere::new();
+        let c = Csg::intersection(
+            s,
+            Cube::new().transform(Matrix::scaling_matrix(2.0, 2.0, 2.0)),
+        )
+        .transform(Matrix::translation_matrix(0.0, 0.0, 5.0));
+        let r = Ray::new(point!(0, 0, 0), vector!(0, 0, 1));
+        let xs = c.intersect(&r);
+        assert_eq!(xs.len(), 2);
+        assert_eq!(xs[0].get_t(), 4.0);
+
+        let comps = xs[0].prepare_computations(&r, &xs);
+        assert_eq!(comps.normalv, vector!(0, 0, -1))
+    }
+}
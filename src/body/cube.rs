@@ -0,0 +1,257 @@
+use uuid::Uuid;
+
+use crate::{
+    bounds::BoundingBox,
+    material::Material,
+    matrix::Matrix,
+    point,
+    ray::{intersection::Intersection, Ray},
+    tuple::{Position, Tuple},
+    utils::EPSILON,
+};
+
+use super::Body;
+
+/// Axis-aligned cube spanning `-1..=1` on every axis in object space.
+#[derive(Clone, Debug)]
+pub struct Cube {
+    id: Uuid,
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    inverse_transpose: Matrix<4, 4>,
+    material: Material,
+}
+
+impl Body for Cube {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            id: Uuid::new_v4(),
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            inverse_transpose: Matrix::identity_matrix(),
+            material: Material::default(),
+        }
+    }
+
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let ray = ray.transform(self.inverse);
+        let origin = ray.get_origin();
+        let direction = ray.get_direction();
+
+        let (xtmin, xtmax) = Self::check_axis(origin[Position::X], direction[Position::X]);
+        let (ytmin, ytmax) = Self::check_axis(origin[Position::Y], direction[Position::Y]);
+        let (ztmin, ztmax) = Self::check_axis(origin[Position::Z], direction[Position::Z]);
+
+        let tmin = xtmin.max(ytmin).max(ztmin);
+        let tmax = xtmax.min(ytmax).min(ztmax);
+
+        if tmin > tmax {
+            return vec![];
+        }
+
+        vec![Intersection::new(tmin, self), Intersection::new(tmax, self)]
+    }
+
+    fn transform(&self, by: Matrix<4, 4>) -> Self
+    where
+        Self: Sized,
+    {
+        let transformation = self.transformation * by;
+        let inverse = transformation.inverse();
+        Self {
+            transformation,
+            inverse,
+            inverse_transpose: inverse.transpose(),
+            ..self.clone()
+        }
+    }
+
+    fn normal_at(&self, point: Tuple) -> Tuple {
+        // convert to object space
+        let point = self.inverse * point;
+
+        let x = point[Position::X];
+        let y = point[Position::Y];
+        let z = point[Position::Z];
+        let maxc = x.abs().max(y.abs()).max(z.abs());
+
+        // the face is determined by the component with the largest absolute value
+        let object_normal = if maxc == x.abs() {
+            Tuple::new_vec(x, 0.0, 0.0)
+        } else if maxc == y.abs() {
+            Tuple::new_vec(0.0, y, 0.0)
+        } else {
+            Tuple::new_vec(0.0, 0.0, z)
+        };
+
+        let world_normal = self.inverse_transpose * object_normal;
+        // WORKAROUND! page 82
+        Tuple::new_vec(
+            world_normal[Position::X],
+            world_normal[Position::Y],
+            world_normal[Position::Z],
+        )
+        .normalize()
+    }
+
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+
+    fn get_inverse_transpose(&self) -> Matrix<4, 4> {
+        self.inverse_transpose
+    }
+
+    fn get_bounds(&self) -> BoundingBox {
+        BoundingBox::new(point!(-1, -1, -1), point!(1, 1, 1))
+    }
+
+    fn get_material(&self) -> Material {
+        self.material.clone()
+    }
+
+    fn set_material(&self, material: Material) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            material,
+            ..self.clone()
+        }
+    }
+}
+
+impl Cube {
+    /// Returns the `t` values at which the ray crosses the two slab planes of one axis.
+    fn check_axis(origin: f64, direction: f64) -> (f64, f64) {
+        let tmin_numerator = -1f64 - origin;
+        let tmax_numerator = 1f64 - origin;
+
+        // dividing by (almost) zero yields infinities with the correct sign
+        let (tmin, tmax) = if direction.abs() >= EPSILON {
+            (tmin_numerator / direction, tmax_numerator / direction)
+        } else {
+            (
+                tmin_numerator * f64::INFINITY,
+                tmax_numerator * f64::INFINITY,
+            )
+        };
+
+        if tmin > tmax {
+            (tmax, tmin)
+        } else {
+            (tmin, tmax)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::{
+        body::Body,
+        material::Material,
+        matrix::{transformation::Axis, Matrix},
+        point,
+        ray::Ray,
+        tuple::Tuple,
+        vector,
+    };
+
+    use super::Cube;
+
+    #[test]
+    fn ray_intersects_cube() {
+        let c = Cube::new();
+        let cases = [
+            (point!(5, 0.5, 0), vector!(-1, 0, 0), 4.0, 6.0),
+            (point!(-5, 0.5, 0), vector!(1, 0, 0), 4.0, 6.0),
+            (point!(0.5, 5, 0), vector!(0, -1, 0), 4.0, 6.0),
+            (point!(0.5, -5, 0), vector!(0, 1, 0), 4.0, 6.0),
+            (point!(0.5, 0, 5), vector!(0, 0, -1), 4.0, 6.0),
+            (point!(0.5, 0, -5), vector!(0, 0, 1), 4.0, 6.0),
+            (point!(0, 0.5, 0), vector!(0, 0, 1), -1.0, 1.0),
+        ];
+
+        for (origin, direction, t1, t2) in cases {
+            let r = Ray::new(origin, direction);
+            let xs = c.intersect(&r);
+            assert_eq!(xs.len(), 2);
+            assert_eq!(xs[0].get_t(), t1);
+            assert_eq!(xs[1].get_t(), t2)
+        }
+    }
+
+    #[test]
+    fn ray_misses_cube() {
+        let c = Cube::new();
+        let cases = [
+            (point!(-2, 0, 0), vector!(0.2673, 0.5345, 0.8018)),
+            (point!(0, -2, 0), vector!(0.8018, 0.2673, 0.5345)),
+            (point!(0, 0, -2), vector!(0.5345, 0.8018, 0.2673)),
+            (point!(2, 0, 2), vector!(0, 0, -1)),
+            (point!(0, 2, 2), vector!(0, -1, 0)),
+            (point!(2, 2, 0), vector!(-1, 0, 0)),
+        ];
+
+        for (origin, direction) in cases {
+            let r = Ray::new(origin, direction);
+            assert!(c.intersect(&r).is_empty())
+        }
+    }
+
+    #[test]
+    fn intersecting_translated_cube_with_ray() {
+        let r = Ray::new(point!(0, 0, -5), vector!(0, 0, 1));
+        let c = Cube::new().transform(Matrix::translation_matrix(0.0, 0.0, 2.0));
+        let xs = c.intersect(&r);
+        assert_eq!(xs[0].get_t(), 6.0);
+        assert_eq!(xs[1].get_t(), 8.0);
+        assert_eq!(xs[0].get_object().get_id(), c.get_id())
+    }
+
+    #[test]
+    fn normal_on_surface_of_cube() {
+        let c = Cube::new();
+        let cases = [
+            (point!(1, 0.5, -0.8), vector!(1, 0, 0)),
+            (point!(-1, -0.2, 0.9), vector!(-1, 0, 0)),
+            (point!(-0.4, 1, -0.1), vector!(0, 1, 0)),
+            (point!(0.3, -1, -0.7), vector!(0, -1, 0)),
+            (point!(-0.6, 0.3, 1), vector!(0, 0, 1)),
+            (point!(0.4, 0.4, -1), vector!(0, 0, -1)),
+            (point!(1, 1, 1), vector!(1, 0, 0)),
+            (point!(-1, -1, -1), vector!(-1, 0, 0)),
+        ];
+
+        for (point, normal) in cases {
+            assert_eq!(c.normal_at(point), normal)
+        }
+    }
+
+    #[test]
+    fn normal_on_transformed_cube() {
+        let c = Cube::new().transform(Matrix::rotation_matrix(Axis::Y, PI / 4.0));
+        let n = c.normal_at(point!(2f64.sqrt() / 2.0, 0, -2f64.sqrt() / 2.0));
+        assert_eq!(n, vector!(2f64.sqrt() / 2.0, 0, -2f64.sqrt() / 2.0))
+    }
+
+    #[test]
+    fn cube_may_be_assigned_material() {
+        let m = Material::default().set_ambient(1.0);
+        let c = Cube::new().set_material(m.clone());
+        assert_eq!(c.get_material(), m)
+    }
+}
@@ -0,0 +1,352 @@
+use uuid::Uuid;
+
+use crate::{
+    bounds::BoundingBox,
+    material::Material,
+    matrix::Matrix,
+    point,
+    ray::{intersection::Intersection, Ray},
+    tuple::{Position, Tuple},
+    utils::EPSILON,
+};
+
+use super::Body;
+
+/// Cylinder of radius 1 around the y axis in object space.
+///
+/// Infinitely long by default; truncated by `minimum` and `maximum` (both exclusive)
+/// and capped at both ends when `closed`.
+#[derive(Clone, Debug)]
+pub struct Cylinder {
+    id: Uuid,
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    inverse_transpose: Matrix<4, 4>,
+    material: Material,
+    minimum: f64,
+    maximum: f64,
+    closed: bool,
+}
+
+impl Body for Cylinder {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            id: Uuid::new_v4(),
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            inverse_transpose: Matrix::identity_matrix(),
+            material: Material::default(),
+            minimum: f64::NEG_INFINITY,
+            maximum: f64::INFINITY,
+            closed: false,
+        }
+    }
+
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let ray = ray.transform(self.inverse);
+        let origin = ray.get_origin();
+        let direction = ray.get_direction();
+        let mut intersections = vec![];
+
+        let a = direction[Position::X].powi(2) + direction[Position::Z].powi(2);
+
+        // ray parallel to the y axis can only hit the caps
+        if a.abs() >= EPSILON {
+            let b = 2f64 * origin[Position::X] * direction[Position::X]
+                + 2f64 * origin[Position::Z] * direction[Position::Z];
+            let c = origin[Position::X].powi(2) + origin[Position::Z].powi(2) - 1f64;
+            let discriminant = b.powi(2) - 4f64 * a * c;
+
+            if discriminant < 0f64 {
+                return intersections;
+            }
+
+            let mut t0 = (-b - discriminant.sqrt()) / (2f64 * a);
+            let mut t1 = (-b + discriminant.sqrt()) / (2f64 * a);
+            if t0 > t1 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+
+            for t in [t0, t1] {
+                let y = origin[Position::Y] + t * direction[Position::Y];
+                if self.minimum < y && y < self.maximum {
+                    intersections.push(Intersection::new(t, self));
+                }
+            }
+        }
+
+        self.intersect_caps(&ray, &mut intersections);
+        intersections
+    }
+
+    fn transform(&self, by: Matrix<4, 4>) -> Self
+    where
+        Self: Sized,
+    {
+        let transformation = self.transformation * by;
+        let inverse = transformation.inverse();
+        Self {
+            transformation,
+            inverse,
+            inverse_transpose: inverse.transpose(),
+            ..self.clone()
+        }
+    }
+
+    fn normal_at(&self, point: Tuple) -> Tuple {
+        // convert to object space
+        let point = self.inverse * point;
+
+        let distance = point[Position::X].powi(2) + point[Position::Z].powi(2);
+        let object_normal = if distance < 1f64 && point[Position::Y] >= self.maximum - EPSILON {
+            Tuple::new_vec(0.0, 1.0, 0.0)
+        } else if distance < 1f64 && point[Position::Y] <= self.minimum + EPSILON {
+            Tuple::new_vec(0.0, -1.0, 0.0)
+        } else {
+            Tuple::new_vec(point[Position::X], 0.0, point[Position::Z])
+        };
+
+        let world_normal = self.inverse_transpose * object_normal;
+        // WORKAROUND! page 82
+        Tuple::new_vec(
+            world_normal[Position::X],
+            world_normal[Position::Y],
+            world_normal[Position::Z],
+        )
+        .normalize()
+    }
+
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+
+    fn get_inverse_transpose(&self) -> Matrix<4, 4> {
+        self.inverse_transpose
+    }
+
+    fn get_bounds(&self) -> BoundingBox {
+        BoundingBox::new(point!(-1, self.minimum, -1), point!(1, self.maximum, 1))
+    }
+
+    fn get_material(&self) -> Material {
+        self.material.clone()
+    }
+
+    fn set_material(&self, material: Material) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            material,
+            ..self.clone()
+        }
+    }
+}
+
+impl Cylinder {
+    pub fn set_minimum(&self, minimum: f64) -> Self {
+        Self {
+            minimum,
+            ..self.clone()
+        }
+    }
+
+    pub fn set_maximum(&self, maximum: f64) -> Self {
+        Self {
+            maximum,
+            ..self.clone()
+        }
+    }
+
+    pub fn set_closed(&self, closed: bool) -> Self {
+        Self {
+            closed,
+            ..self.clone()
+        }
+    }
+
+    pub fn get_minimum(&self) -> f64 {
+        self.minimum
+    }
+
+    pub fn get_maximum(&self) -> f64 {
+        self.maximum
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Checks whether the intersection at `t` lies within the radius of the cylinder.
+    fn check_cap(ray: &Ray, t: f64) -> bool {
+        let x = ray.get_origin()[Position::X] + t * ray.get_direction()[Position::X];
+        let z = ray.get_origin()[Position::Z] + t * ray.get_direction()[Position::Z];
+        x.powi(2) + z.powi(2) <= 1f64
+    }
+
+    fn intersect_caps<'a>(&'a self, ray: &Ray, intersections: &mut Vec<Intersection<'a>>) {
+        // caps only matter if the cylinder is closed and could be intersected
+        if !self.closed || ray.get_direction()[Position::Y].abs() < EPSILON {
+            return;
+        }
+
+        for y in [self.minimum, self.maximum] {
+            let t = (y - ray.get_origin()[Position::Y]) / ray.get_direction()[Position::Y];
+            if Self::check_cap(ray, t) {
+                intersections.push(Intersection::new(t, self));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        body::Body, matrix::Matrix, point, ray::Ray, tuple::Tuple, utils::assert_f64_eq, vector,
+    };
+
+    use super::Cylinder;
+
+    #[test]
+    fn ray_misses_cylinder() {
+        let cyl = Cylinder::new();
+        let cases = [
+            (point!(1, 0, 0), vector!(0, 1, 0)),
+            (point!(0, 0, 0), vector!(0, 1, 0)),
+            (point!(0, 0, -5), vector!(1, 1, 1)),
+        ];
+
+        for (origin, direction) in cases {
+            let r = Ray::new(origin, direction.normalize());
+            assert!(cyl.intersect(&r).is_empty())
+        }
+    }
+
+    #[test]
+    fn ray_strikes_cylinder() {
+        let cyl = Cylinder::new();
+        let cases = [
+            (point!(1, 0, -5), vector!(0, 0, 1), 5.0, 5.0),
+            (point!(0, 0, -5), vector!(0, 0, 1), 4.0, 6.0),
+            (point!(0.5, 0, -5), vector!(0.1, 1, 1), 6.80798, 7.08872),
+        ];
+
+        for (origin, direction, t0, t1) in cases {
+            let r = Ray::new(origin, direction.normalize());
+            let xs = cyl.intersect(&r);
+            assert_eq!(xs.len(), 2);
+            assert_f64_eq!(xs[0].get_t(), t0);
+            assert_f64_eq!(xs[1].get_t(), t1)
+        }
+    }
+
+    #[test]
+    fn normal_on_cylinder() {
+        let cyl = Cylinder::new();
+        let cases = [
+            (point!(1, 0, 0), vector!(1, 0, 0)),
+            (point!(0, 5, -1), vector!(0, 0, -1)),
+            (point!(0, -2, 1), vector!(0, 0, 1)),
+            (point!(-1, 1, 0), vector!(-1, 0, 0)),
+        ];
+
+        for (point, normal) in cases {
+            assert_eq!(cyl.normal_at(point), normal)
+        }
+    }
+
+    #[test]
+    fn default_cylinder_is_infinite_and_open() {
+        let cyl = Cylinder::new();
+        assert_eq!(cyl.get_minimum(), f64::NEG_INFINITY);
+        assert_eq!(cyl.get_maximum(), f64::INFINITY);
+        assert!(!cyl.is_closed())
+    }
+
+    #[test]
+    fn intersecting_constrained_cylinder() {
+        let cyl = Cylinder::new().set_minimum(1.0).set_maximum(2.0);
+        let cases = [
+            (point!(0, 1.5, 0), vector!(0.1, 1, 0), 0),
+            (point!(0, 3, -5), vector!(0, 0, 1), 0),
+            (point!(0, 0, -5), vector!(0, 0, 1), 0),
+            (point!(0, 2, -5), vector!(0, 0, 1), 0),
+            (point!(0, 1, -5), vector!(0, 0, 1), 0),
+            (point!(0, 1.5, -2), vector!(0, 0, 1), 2),
+        ];
+
+        for (origin, direction, count) in cases {
+            let r = Ray::new(origin, direction.normalize());
+            assert_eq!(cyl.intersect(&r).len(), count)
+        }
+    }
+
+    #[test]
+    fn intersecting_caps_of_closed_cylinder() {
+        let cyl = Cylinder::new()
+            .set_minimum(1.0)
+            .set_maximum(2.0)
+            .set_closed(true);
+        let cases = [
+            (point!(0, 3, 0), vector!(0, -1, 0), 2),
+            (point!(0, 3, -2), vector!(0, -1, 2), 2),
+            (point!(0, 4, -2), vector!(0, -1, 1), 2),
+            (point!(0, 0, -2), vector!(0, 1, 2), 2),
+            (point!(0, -1, -2), vector!(0, 1, 1), 2),
+        ];
+
+        for (origin, direction, count) in cases {
+            let r = Ray::new(origin, direction.normalize());
+            assert_eq!(cyl.intersect(&r).len(), count)
+        }
+    }
+
+    #[test]
+    fn normal_on_cylinder_end_caps() {
+        let cyl = Cylinder::new()
+            .set_minimum(1.0)
+            .set_maximum(2.0)
+            .set_closed(true);
+        let cases = [
+            (point!(0, 1, 0), vector!(0, -1, 0)),
+            (point!(0.5, 1, 0), vector!(0, -1, 0)),
+            (point!(0, 1, 0.5), vector!(0, -1, 0)),
+            (point!(0, 2, 0), vector!(0, 1, 0)),
+            (point!(0.5, 2, 0), vector!(0, 1, 0)),
+            (point!(0, 2, 0.5), vector!(0, 1, 0)),
+        ];
+
+        for (point, normal) in cases {
+            assert_eq!(cyl.normal_at(point), normal)
+        }
+    }
+
+    #[test]
+    fn intersecting_scaled_cylinder() {
+        let cyl = Cylinder::new().transform(Matrix::scaling_matrix(2.0, 1.0, 2.0));
+        let r = Ray::new(point!(0, 0, -5), vector!(0, 0, 1));
+        let xs = cyl.intersect(&r);
+        assert_eq!(xs.len(), 2);
+        assert_eq!(xs[0].get_t(), 3.0);
+        assert_eq!(xs[1].get_t(), 7.0)
+    }
+
+    #[test]
+    fn bounds_of_truncated_cylinder() {
+        let cyl = Cylinder::new().set_minimum(-5.0).set_maximum(3.0);
+        let b = cyl.get_bounds();
+        assert_eq!(b.get_min(), point!(-1, -5, -1));
+        assert_eq!(b.get_max(), point!(1, 3, 1))
+    }
+}
@@ -0,0 +1,313 @@
+use std::sync::{Arc, OnceLock};
+
+use uuid::Uuid;
+
+use crate::{
+    bounds::BoundingBox,
+    bvh::Bvh,
+    material::Material,
+    matrix::Matrix,
+    ray::{intersection::Intersection, Ray},
+    tuple::Tuple,
+};
+
+use super::Body;
+
+/// Collection of bodies transformed as one unit.
+///
+/// The transformation of the group is applied on top of the transformations of its children.
+/// Groups have no surface of their own; the material of a group is not inherited by its
+/// children.
+#[derive(Clone)]
+pub struct Group {
+    id: Uuid,
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    inverse_transpose: Matrix<4, 4>,
+    material: Material,
+    children: Vec<Arc<dyn Body>>,
+    // built on first intersection, as children are only added while constructing the group
+    bvh: OnceLock<Arc<Bvh>>,
+}
+
+impl Body for Group {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            id: Uuid::new_v4(),
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            inverse_transpose: Matrix::identity_matrix(),
+            material: Material::default(),
+            children: vec![],
+            bvh: OnceLock::new(),
+        }
+    }
+
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let ray = ray.transform(self.inverse);
+
+        let mut intersections: Vec<Intersection> = self
+            .get_bvh()
+            .candidates(&ray)
+            .into_iter()
+            .flat_map(|index| self.children[index].intersect(&ray))
+            .map(|intersection| intersection.with_parent(self.inverse))
+            .collect();
+
+        intersections.sort_by(|a, b| a.get_t().total_cmp(&b.get_t()));
+        intersections
+    }
+
+    fn transform(&self, by: Matrix<4, 4>) -> Self
+    where
+        Self: Sized,
+    {
+        let transformation = self.transformation * by;
+        let inverse = transformation.inverse();
+        Self {
+            transformation,
+            inverse,
+            inverse_transpose: inverse.transpose(),
+            ..self.clone()
+        }
+    }
+
+    fn normal_at(&self, _point: Tuple) -> Tuple {
+        panic!("Groups have no surface, normals are computed on their children.")
+    }
+
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn includes(&self, body: &dyn Body) -> bool {
+        std::ptr::addr_eq(self, body) || self.children.iter().any(|child| child.includes(body))
+    }
+
+    fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+
+    fn get_inverse_transpose(&self) -> Matrix<4, 4> {
+        self.inverse_transpose
+    }
+
+    fn get_bounds(&self) -> BoundingBox {
+        self.children
+            .iter()
+            .fold(BoundingBox::empty(), |bounds, child| {
+                bounds.merge(&child.get_parent_space_bounds())
+            })
+    }
+
+    fn get_material(&self) -> Material {
+        self.material.clone()
+    }
+
+    fn set_material(&self, material: Material) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            material,
+            ..self.clone()
+        }
+    }
+}
+
+impl Group {
+    /// Creates a group of many children at once; prefer this over repeated `add_child`, which
+    /// copies the children every time.
+    pub fn from_children(children: Vec<Arc<dyn Body>>) -> Self {
+        Self {
+            children,
+            ..Self::new()
+        }
+    }
+
+    pub fn add_child<B: Body + 'static>(&self, child: B) -> Self {
+        let mut children = self.children.clone();
+        children.push(Arc::new(child));
+        Self {
+            id: self.id,
+            transformation: self.transformation,
+            inverse: self.inverse,
+            inverse_transpose: self.inverse_transpose,
+            material: self.material.clone(),
+            children,
+            bvh: OnceLock::new(),
+        }
+    }
+
+    pub fn get_children(&self) -> Vec<&dyn Body> {
+        self.children.iter().map(|child| child.as_ref()).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    fn get_bvh(&self) -> &Bvh {
+        self.bvh.get_or_init(|| {
+            let bounds: Vec<BoundingBox> = self
+                .children
+                .iter()
+                .map(|child| child.get_parent_space_bounds())
+                .collect();
+            Arc::new(Bvh::build(&bounds))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{f64::consts::PI, sync::Arc};
+
+    use crate::{
+        body::{cube::Cube, sphere::Sphere, Body},
+        matrix::{transformation::Axis, Matrix},
+        point,
+        ray::{intersection::Intersection, Ray},
+        tuple::Tuple,
+        vector,
+    };
+
+    use super::Group;
+
+    #[test]
+    fn creating_new_group() {
+        let g = Group::new();
+        assert_eq!(g.get_transformation(), Matrix::identity_matrix());
+        assert!(g.is_empty())
+    }
+
+    #[test]
+    fn adding_child_to_group() {
+        let s = Sphere::new();
+        let g = Group::new().add_child(s.clone());
+        assert!(!g.is_empty());
+        assert_eq!(g.get_children()[0].get_id(), s.get_id())
+    }
+
+    #[test]
+    fn creating_group_from_children() {
+        let s = Sphere::new();
+        let c = Cube::new();
+        let g = Group::from_children(vec![Arc::new(s.clone()), Arc::new(c.clone())]);
+        let children = g.get_children();
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].get_id(), s.get_id());
+        assert_eq!(children[1].get_id(), c.get_id())
+    }
+
+    #[test]
+    fn group_includes_nested_children() {
+        let s = Sphere::new();
+        let child: Arc<dyn Body> = Arc::new(s.clone());
+        let g = Group::new().add_child(Group::from_children(vec![child.clone()]));
+        assert!(g.includes(&g));
+        assert!(g.includes(child.as_ref()));
+        // copies share the id, but are different bodies
+        assert!(!g.includes(&s))
+    }
+
+    #[test]
+    fn intersecting_ray_with_empty_group() {
+        let g = Group::new();
+        let r = Ray::new(point!(0, 0, 0), vector!(0, 0, 1));
+        assert!(g.intersect(&r).is_empty())
+    }
+
+    #[test]
+    fn intersecting_ray_with_nonempty_group() {
+        let s1 = Sphere::new();
+        let s2 = Sphere::new().transform(Matrix::translation_matrix(0.0, 0.0, -3.0));
+        let s3 = Sphere::new().transform(Matrix::translation_matrix(5.0, 0.0, 0.0));
+        let g = Group::new()
+            .add_child(s1.clone())
+            .add_child(s2.clone())
+            .add_child(s3);
+        let r = Ray::new(point!(0, 0, -5), vector!(0, 0, 1));
+        let xs = g.intersect(&r);
+        assert_eq!(xs.len(), 4);
+        assert_eq!(xs[0].get_object().get_id(), s2.get_id());
+        assert_eq!(xs[1].get_object().get_id(), s2.get_id());
+        assert_eq!(xs[2].get_object().get_id(), s1.get_id());
+        assert_eq!(xs[3].get_object().get_id(), s1.get_id())
+    }
+
+    #[test]
+    fn intersecting_transformed_group() {
+        let s = Sphere::new().transform(Matrix::translation_matrix(5.0, 0.0, 0.0));
+        let g = Group::new()
+            .add_child(s)
+            .transform(Matrix::scaling_matrix(2.0, 2.0, 2.0));
+        let r = Ray::new(point!(10, 0, -10), vector!(0, 0, 1));
+        assert_eq!(g.intersect(&r).len(), 2)
+    }
+
+    #[test]
+    fn converting_point_from_world_to_object_space() {
+        let s = Sphere::new().transform(Matrix::translation_matrix(5.0, 0.0, 0.0));
+        // inner group scaled, outer group rotated
+        let i = Intersection::new(1.0, &s)
+            .with_parent(Matrix::scaling_matrix(2.0, 2.0, 2.0).inverse())
+            .with_parent(Matrix::rotation_matrix(Axis::Y, PI / 2.0).inverse());
+        let p = i.world_to_object(point!(-2, 0, -10));
+        assert_eq!(s.get_inverse() * p, point!(0, 0, -1))
+    }
+
+    #[test]
+    fn converting_normal_from_object_to_world_space() {
+        let s = Sphere::new().transform(Matrix::translation_matrix(5.0, 0.0, 0.0));
+        let i = Intersection::new(1.0, &s)
+            .with_parent(Matrix::scaling_matrix(1.0, 2.0, 3.0).inverse())
+            .with_parent(Matrix::rotation_matrix(Axis::Y, PI / 2.0).inverse());
+        let n = i.normal_to_world(vector!(
+            3f64.sqrt() / 3.0,
+            3f64.sqrt() / 3.0,
+            3f64.sqrt() / 3.0
+        ));
+        assert_eq!(n, vector!(0.28571, 0.42857, -0.85714))
+    }
+
+    #[test]
+    fn finding_normal_on_child_of_nested_group() {
+        let s = Sphere::new().transform(Matrix::translation_matrix(5.0, 0.0, 0.0));
+        let g2 = Group::new()
+            .add_child(s.clone())
+            .transform(Matrix::scaling_matrix(2.0, 2.0, 2.0));
+        let g1 = Group::new()
+            .add_child(g2)
+            .transform(Matrix::rotation_matrix(Axis::Y, PI / 2.0));
+
+        // sphere ends up with radius 2 around (0, 0, -10) in world space
+        let r = Ray::new(point!(0, 0, 0), vector!(0, 0, -1));
+        let xs = g1.intersect(&r);
+        assert_eq!(xs.len(), 2);
+        assert_eq!(xs[0].get_t(), 8.0);
+        assert_eq!(xs[0].get_object().get_id(), s.get_id());
+
+        let comps = xs[0].prepare_computations(&r, &xs);
+        assert_eq!(comps.point, point!(0, 0, -8));
+        assert_eq!(comps.normalv, vector!(0, 0, 1))
+    }
+
+    #[test]
+    fn group_bounds_contain_all_children() {
+        let s = Sphere::new()
+            .transform(Matrix::translation_matrix(2.0, 5.0, -3.0).scale(2.0, 2.0, 2.0));
+        let c = Cube::new().transform(Matrix::translation_matrix(-4.0, -1.0, 4.0));
+        let g = Group::new().add_child(s).add_child(c);
+        let b = g.get_bounds();
+        assert_eq!(b.get_min(), point!(-5, -2, -5));
+        assert_eq!(b.get_max(), point!(4, 7, 5))
+    }
+}
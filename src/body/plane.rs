@@ -1,17 +1,20 @@
 use crate::body::Body;
+use crate::bounds::BoundingBox;
 use crate::material::Material;
 use crate::matrix::Matrix;
 use crate::ray::intersection::Intersection;
 use crate::ray::Ray;
 use crate::tuple::{Position, Tuple};
 use crate::utils::EPSILON;
-use crate::vector;
+use crate::{point, vector};
 use uuid::Uuid;
 
 #[derive(Clone, Debug)]
 pub struct Plane {
     id: Uuid,
     transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    inverse_transpose: Matrix<4, 4>,
     material: Material,
 }
 
@@ -23,31 +26,46 @@ impl Body for Plane {
         Self {
             id: Uuid::new_v4(),
             transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            inverse_transpose: Matrix::identity_matrix(),
             material: Material::default(),
         }
     }
 
-    fn intersect(&self, ray: &Ray) -> Option<[Intersection; 2]> {
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let ray = ray.transform(self.inverse);
+
         if ray.get_direction()[Position::Y].abs() < EPSILON {
-            return None;
+            return vec![];
         }
 
         let t = -ray.get_origin()[Position::Y] / ray.get_direction()[Position::Y];
-        Some([Intersection::new(t, self); 2]) // TODO: change to array slice
+        vec![Intersection::new(t, self)]
     }
 
     fn transform(&self, by: Matrix<4, 4>) -> Self
     where
         Self: Sized,
     {
+        let transformation = self.transformation * by;
+        let inverse = transformation.inverse();
         Self {
-            transformation: self.transformation * by,
-            ..*self
+            transformation,
+            inverse,
+            inverse_transpose: inverse.transpose(),
+            ..self.clone()
         }
     }
 
-    fn normal_at(&self, point: Tuple) -> Tuple {
-        vector!(0, 1, 0)
+    fn normal_at(&self, _point: Tuple) -> Tuple {
+        let world_normal = self.inverse_transpose * vector!(0, 1, 0);
+        // WORKAROUND! page 82
+        vector!(
+            world_normal[Position::X],
+            world_normal[Position::Y],
+            world_normal[Position::Z]
+        )
+        .normalize()
     }
 
     fn get_id(&self) -> Uuid {
@@ -58,22 +76,43 @@ impl Body for Plane {
         self.transformation
     }
 
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+
+    fn get_inverse_transpose(&self) -> Matrix<4, 4> {
+        self.inverse_transpose
+    }
+
+    fn get_bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            point!(f64::NEG_INFINITY, 0, f64::NEG_INFINITY),
+            point!(f64::INFINITY, 0, f64::INFINITY),
+        )
+    }
+
     fn get_material(&self) -> Material {
-        self.material
+        self.material.clone()
     }
 
     fn set_material(&self, material: Material) -> Self
     where
         Self: Sized,
     {
-        Self { material, ..*self }
+        Self {
+            material,
+            ..self.clone()
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::f64::consts::PI;
+
     use crate::body::plane::Plane;
     use crate::body::Body;
+    use crate::matrix::{transformation::Axis, Matrix};
     use crate::ray::Ray;
     use crate::tuple::Tuple;
     use crate::{point, vector};
@@ -94,7 +133,7 @@ mod tests {
         let plane = Plane::new();
         let ray = Ray::new(point!(0, 10, 0), vector!(0, 0, 1));
         let xs = plane.intersect(&ray);
-        assert!(xs.is_none());
+        assert!(xs.is_empty());
     }
 
     #[test]
@@ -102,7 +141,7 @@ mod tests {
         let plane = Plane::new();
         let ray = Ray::new(point!(0, 0, 0), vector!(0, 0, 1));
         let xs = plane.intersect(&ray);
-        assert!(xs.is_none());
+        assert!(xs.is_empty());
     }
 
     #[test]
@@ -110,8 +149,7 @@ mod tests {
         let plane = Plane::new();
         let ray = Ray::new(point!(0, 1, 0), vector!(0, -1, 0));
         let xs = plane.intersect(&ray);
-        let xs = xs.unwrap();
-        assert_eq!(xs.len(), 2); // TODO: has to be 1, not possible with fixed size array
+        assert_eq!(xs.len(), 1);
         assert_eq!(xs[0].get_t(), 1.0);
         assert_eq!(xs[0].get_object().get_id(), plane.get_id());
     }
@@ -121,9 +159,23 @@ mod tests {
         let plane = Plane::new();
         let ray = Ray::new(point!(0, -1, 0), vector!(0, 1, 0));
         let xs = plane.intersect(&ray);
-        let xs = xs.unwrap();
-        assert_eq!(xs.len(), 2); // TODO: has to be 1, not possible with fixed size array
+        assert_eq!(xs.len(), 1);
         assert_eq!(xs[0].get_t(), 1.0);
         assert_eq!(xs[0].get_object().get_id(), plane.get_id());
     }
+
+    #[test]
+    fn intersecting_translated_plane() {
+        let plane = Plane::new().transform(Matrix::translation_matrix(0.0, -1.0, 0.0));
+        let ray = Ray::new(point!(0, 1, 0), vector!(0, -1, 0));
+        let xs = plane.intersect(&ray);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].get_t(), 2.0);
+    }
+
+    #[test]
+    fn normal_of_rotated_plane() {
+        let plane = Plane::new().transform(Matrix::rotation_matrix(Axis::Z, PI / 2.0));
+        assert_eq!(plane.normal_at(point!(0, 0, 0)), vector!(-1, 0, 0));
+    }
 }
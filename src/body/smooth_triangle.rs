@@ -0,0 +1,245 @@
+use uuid::Uuid;
+
+use crate::{
+    bounds::BoundingBox,
+    material::Material,
+    matrix::Matrix,
+    point,
+    ray::{intersection::Intersection, Ray},
+    tuple::{Position, Tuple},
+    vector,
+};
+
+use super::{triangle::intersect_barycentric, Body};
+
+/// Triangle with a normal per corner, interpolated across the surface.
+///
+/// Used to approximate curved surfaces, e.g. from meshes with vertex normals.
+#[derive(Clone, Debug)]
+pub struct SmoothTriangle {
+    id: Uuid,
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    inverse_transpose: Matrix<4, 4>,
+    material: Material,
+    p1: Tuple,
+    p2: Tuple,
+    p3: Tuple,
+    n1: Tuple,
+    n2: Tuple,
+    n3: Tuple,
+    e1: Tuple,
+    e2: Tuple,
+}
+
+impl Body for SmoothTriangle {
+    /// Creates the triangle `(0, 1, 0)`, `(-1, 0, 0)`, `(1, 0, 0)` with normals pointing
+    /// away from its center.
+    /// Use `SmoothTriangle::from_points` for arbitrary triangles.
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_points(
+            point!(0, 1, 0),
+            point!(-1, 0, 0),
+            point!(1, 0, 0),
+            vector!(0, 1, 0),
+            vector!(-1, 0, 0),
+            vector!(1, 0, 0),
+        )
+    }
+
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let ray = ray.transform(self.inverse);
+
+        match intersect_barycentric(self.p1, self.e1, self.e2, &ray) {
+            Some((t, u, v)) => vec![Intersection::new_with_uv(t, self, u, v)],
+            None => vec![],
+        }
+    }
+
+    fn transform(&self, by: Matrix<4, 4>) -> Self
+    where
+        Self: Sized,
+    {
+        let transformation = self.transformation * by;
+        let inverse = transformation.inverse();
+        Self {
+            transformation,
+            inverse,
+            inverse_transpose: inverse.transpose(),
+            ..self.clone()
+        }
+    }
+
+    fn normal_at(&self, point: Tuple) -> Tuple {
+        // without an intersection the barycentric coordinates are recovered from the point
+        let point = self.inverse * point;
+        let (u, v) = self.barycentric(point);
+        self.interpolated_normal(u, v)
+    }
+
+    fn normal_at_intersection(&self, point: Tuple, intersection: &Intersection) -> Tuple {
+        match intersection.get_uv() {
+            Some((u, v)) => self.interpolated_normal(u, v),
+            None => self.normal_at(point),
+        }
+    }
+
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+
+    fn get_inverse_transpose(&self) -> Matrix<4, 4> {
+        self.inverse_transpose
+    }
+
+    fn get_bounds(&self) -> BoundingBox {
+        BoundingBox::empty()
+            .add_point(self.p1)
+            .add_point(self.p2)
+            .add_point(self.p3)
+    }
+
+    fn get_material(&self) -> Material {
+        self.material.clone()
+    }
+
+    fn set_material(&self, material: Material) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            material,
+            ..self.clone()
+        }
+    }
+}
+
+impl SmoothTriangle {
+    pub fn from_points(p1: Tuple, p2: Tuple, p3: Tuple, n1: Tuple, n2: Tuple, n3: Tuple) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            inverse_transpose: Matrix::identity_matrix(),
+            material: Material::default(),
+            p1,
+            p2,
+            p3,
+            n1,
+            n2,
+            n3,
+            e1: p2 - p1,
+            e2: p3 - p1,
+        }
+    }
+
+    pub fn get_points(&self) -> (Tuple, Tuple, Tuple) {
+        (self.p1, self.p2, self.p3)
+    }
+
+    pub fn get_normals(&self) -> (Tuple, Tuple, Tuple) {
+        (self.n1, self.n2, self.n3)
+    }
+
+    /// Barycentric coordinates `(u, v)` of an object space point lying on the triangle.
+    fn barycentric(&self, point: Tuple) -> (f64, f64) {
+        let p1_to_point = point - self.p1;
+        let d00 = self.e1.dot(self.e1);
+        let d01 = self.e1.dot(self.e2);
+        let d11 = self.e2.dot(self.e2);
+        let d20 = p1_to_point.dot(self.e1);
+        let d21 = p1_to_point.dot(self.e2);
+        let denominator = d00 * d11 - d01 * d01;
+
+        (
+            (d11 * d20 - d01 * d21) / denominator,
+            (d00 * d21 - d01 * d20) / denominator,
+        )
+    }
+
+    fn interpolated_normal(&self, u: f64, v: f64) -> Tuple {
+        let object_normal = self.n2 * u + self.n3 * v + self.n1 * (1f64 - u - v);
+
+        let world_normal = self.inverse_transpose * object_normal;
+        // WORKAROUND! page 82
+        Tuple::new_vec(
+            world_normal[Position::X],
+            world_normal[Position::Y],
+            world_normal[Position::Z],
+        )
+        .normalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        body::Body,
+        point,
+        ray::{intersection::Intersection, Ray},
+        tuple::Tuple,
+        utils::assert_f64_eq,
+        vector,
+    };
+
+    use super::SmoothTriangle;
+
+    #[test]
+    fn constructing_smooth_triangle() {
+        let tri = SmoothTriangle::new();
+        assert_eq!(
+            tri.get_points(),
+            (point!(0, 1, 0), point!(-1, 0, 0), point!(1, 0, 0))
+        );
+        assert_eq!(
+            tri.get_normals(),
+            (vector!(0, 1, 0), vector!(-1, 0, 0), vector!(1, 0, 0))
+        )
+    }
+
+    #[test]
+    fn intersection_with_smooth_triangle_stores_uv() {
+        let tri = SmoothTriangle::new();
+        let r = Ray::new(point!(-0.2, 0.3, -2), vector!(0, 0, 1));
+        let xs = tri.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        let (u, v) = xs[0].get_uv().unwrap();
+        assert_f64_eq!(u, 0.45);
+        assert_f64_eq!(v, 0.25)
+    }
+
+    #[test]
+    fn smooth_triangle_uses_uv_to_interpolate_normal() {
+        let tri = SmoothTriangle::new();
+        let i = Intersection::new_with_uv(1.0, &tri, 0.45, 0.25);
+        let n = tri.normal_at_intersection(point!(0, 0, 0), &i);
+        assert_eq!(n, vector!(-0.5547, 0.83205, 0))
+    }
+
+    #[test]
+    fn smooth_triangle_interpolates_normal_from_point() {
+        let tri = SmoothTriangle::new();
+        let n = tri.normal_at(point!(-0.2, 0.3, 0));
+        assert_eq!(n, vector!(-0.5547, 0.83205, 0))
+    }
+
+    #[test]
+    fn preparing_normal_on_smooth_triangle() {
+        let tri = SmoothTriangle::new();
+        let i = Intersection::new_with_uv(1.0, &tri, 0.45, 0.25);
+        let r = Ray::new(point!(-0.2, 0.3, -2), vector!(0, 0, 1));
+        let comps = i.prepare_computations(&r, &[i]);
+        assert_eq!(comps.normalv, vector!(-0.5547, 0.83205, 0))
+    }
+}
@@ -1,18 +1,22 @@
 use uuid::Uuid;
 
 use crate::{
+    bounds::BoundingBox,
     material::Material,
     matrix::Matrix,
+    point,
     ray::{intersection::Intersection, Ray},
     tuple::{Position, Tuple},
 };
 
 use super::Body;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Debug)]
 pub struct Sphere {
     id: Uuid,
     transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    inverse_transpose: Matrix<4, 4>,
     material: Material,
 }
 
@@ -24,15 +28,17 @@ impl Body for Sphere {
         Self {
             id: Uuid::new_v4(),
             transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            inverse_transpose: Matrix::identity_matrix(),
             material: Material::default(),
         }
     }
 
-    fn intersect(&self, ray: &Ray) -> Option<[Intersection; 2]> {
-        let ray = ray.transform(self.transformation.inverse());
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let ray = ray.transform(self.inverse);
 
         if !self.intersects(&ray) {
-            return None;
+            return vec![];
         }
 
         // TODO: prevent duplication with `discriminant()`
@@ -40,10 +46,10 @@ impl Body for Sphere {
         let a = ray.get_direction().dot(ray.get_direction());
         let b = 2f64 * ray.get_direction().dot(sphere_to_ray);
 
-        Some([
+        vec![
             Intersection::new((-b - self.discriminant(&ray).sqrt()) / (2f64 * a), self),
             Intersection::new((-b + self.discriminant(&ray).sqrt()) / (2f64 * a), self),
-        ])
+        ]
     }
 
     fn get_id(&self) -> Uuid {
@@ -54,22 +60,38 @@ impl Body for Sphere {
         self.transformation
     }
 
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+
+    fn get_inverse_transpose(&self) -> Matrix<4, 4> {
+        self.inverse_transpose
+    }
+
+    fn get_bounds(&self) -> BoundingBox {
+        BoundingBox::new(point!(-1, -1, -1), point!(1, 1, 1))
+    }
+
     fn transform(&self, by: crate::matrix::Matrix<4, 4>) -> Self
     where
         Self: Sized,
     {
+        let transformation = self.transformation * by;
+        let inverse = transformation.inverse();
         Self {
-            transformation: self.transformation * by,
-            ..*self
+            transformation,
+            inverse,
+            inverse_transpose: inverse.transpose(),
+            ..self.clone()
         }
     }
 
     fn normal_at(&self, point: Tuple) -> Tuple {
         // convert to object space
-        let point = self.transformation.inverse() * point;
+        let point = self.inverse * point;
         // left side converts back to world space
         let world_space_normal =
-            self.transformation.inverse().transpose() * (point - Tuple::point_origin()).normalize();
+            self.inverse_transpose * (point - Tuple::point_origin()).normalize();
         // WORKAROUND! page 82
         Tuple::from([
             world_space_normal[Position::X],
@@ -81,14 +103,17 @@ impl Body for Sphere {
     }
 
     fn get_material(&self) -> crate::material::Material {
-        self.material
+        self.material.clone()
     }
 
     fn set_material(&self, material: Material) -> Self
     where
         Self: Sized,
     {
-        Self { material, ..*self }
+        Self {
+            material,
+            ..self.clone()
+        }
     }
 }
 
@@ -121,8 +146,6 @@ mod tests {
 
     use super::Sphere;
 
-    // checking length in every test because of potential future switch to vec
-
     #[test]
     fn ray_intersects_sphere_at_two_points() {
         let r = Ray::new(
@@ -130,7 +153,7 @@ mod tests {
             Tuple::new_vec(0.0, 0.0, 1.0),
         );
         let s = Sphere::new();
-        let xs = s.intersect(&r).unwrap();
+        let xs = s.intersect(&r);
         assert_eq!(xs.len(), 2);
         assert_eq!(xs[0].get_t(), 4.0);
         assert_eq!(xs[1].get_t(), 6.0)
@@ -143,7 +166,7 @@ mod tests {
             Tuple::new_vec(0.0, 0.0, 1.0),
         );
         let s = Sphere::new();
-        let xs = s.intersect(&r).unwrap();
+        let xs = s.intersect(&r);
         assert_eq!(xs.len(), 2);
         assert_eq!(xs[0].get_t(), 5.0);
         assert_eq!(xs[1].get_t(), 5.0);
@@ -157,14 +180,14 @@ mod tests {
         );
         let s = Sphere::new();
         let xs = s.intersect(&r);
-        assert!(xs.is_none())
+        assert!(xs.is_empty())
     }
 
     #[test]
     fn ray_originates_inside_sphere() {
         let r = Ray::new(Tuple::point_origin(), Tuple::new_vec(0.0, 0.0, 1.0));
         let s = Sphere::new();
-        let xs = s.intersect(&r).unwrap();
+        let xs = s.intersect(&r);
         assert_eq!(xs.len(), 2);
         assert_eq!(xs[0].get_t(), -1.0);
         assert_eq!(xs[1].get_t(), 1.0)
@@ -177,7 +200,7 @@ mod tests {
             Tuple::new_vec(0.0, 0.0, 1.0),
         );
         let s = Sphere::new();
-        let xs = s.intersect(&r).unwrap();
+        let xs = s.intersect(&r);
         assert_eq!(xs.len(), 2);
         assert_eq!(xs[0].get_t(), -6.0);
         assert_eq!(xs[1].get_t(), -4.0)
@@ -190,7 +213,7 @@ mod tests {
             Tuple::new_vec(0.0, 0.0, 1.0),
         );
         let s = Sphere::new();
-        let xs = s.intersect(&r).unwrap();
+        let xs = s.intersect(&r);
         assert_eq!(xs.len(), 2);
         assert_eq!(xs[0].get_object().get_id(), s.id);
         assert_eq!(xs[1].get_object().get_id(), s.id)
@@ -209,6 +232,14 @@ mod tests {
         assert_eq!(s.get_transformation(), t)
     }
 
+    #[test]
+    fn transforming_sphere_caches_inverse() {
+        let t = Matrix::scaling_matrix(2.0, 3.0, 4.0).translate(1.0, 0.0, 0.0);
+        let s = Sphere::new().transform(t);
+        assert_eq!(s.get_inverse(), t.inverse());
+        assert_eq!(s.get_inverse_transpose(), t.inverse().transpose())
+    }
+
     #[test]
     fn intersecting_scaled_sphere_with_ray() {
         let r = Ray::new(
@@ -217,9 +248,9 @@ mod tests {
         );
         let s = Sphere::new().transform(Matrix::scaling_matrix(2.0, 2.0, 2.0));
         let xs = s.intersect(&r);
-        assert_eq!(xs.unwrap().len(), 2);
-        assert_eq!(xs.unwrap()[0].get_t(), 3.0);
-        assert_eq!(xs.unwrap()[1].get_t(), 7.0)
+        assert_eq!(xs.len(), 2);
+        assert_eq!(xs[0].get_t(), 3.0);
+        assert_eq!(xs[1].get_t(), 7.0)
     }
 
     #[test]
@@ -230,7 +261,7 @@ mod tests {
         );
         let s = Sphere::new().transform(Matrix::translation_matrix(5.0, 0.0, 0.0));
         let xs = s.intersect(&r);
-        assert!(xs.is_none())
+        assert!(xs.is_empty())
     }
 
     #[test]
@@ -300,7 +331,7 @@ mod tests {
     #[test]
     fn sphere_may_be_assigned_material() {
         let m = Material::default().set_ambient(1.0);
-        let s = Sphere::new().set_material(m);
+        let s = Sphere::new().set_material(m.clone());
         assert_eq!(s.get_material(), m)
     }
 }
@@ -0,0 +1,253 @@
+use uuid::Uuid;
+
+use crate::{
+    bounds::BoundingBox,
+    material::Material,
+    matrix::Matrix,
+    point,
+    ray::{intersection::Intersection, Ray},
+    tuple::{Position, Tuple},
+    utils::EPSILON,
+};
+
+use super::Body;
+
+/// Flat triangle spanned by three points in object space.
+///
+/// Edges and the surface normal are precomputed when the triangle is created.
+#[derive(Clone, Debug)]
+pub struct Triangle {
+    id: Uuid,
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    inverse_transpose: Matrix<4, 4>,
+    material: Material,
+    p1: Tuple,
+    p2: Tuple,
+    p3: Tuple,
+    e1: Tuple,
+    e2: Tuple,
+    normal: Tuple,
+}
+
+impl Body for Triangle {
+    /// Creates the triangle `(0, 1, 0)`, `(-1, 0, 0)`, `(1, 0, 0)`.
+    /// Use `Triangle::from_points` for arbitrary triangles.
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_points(point!(0, 1, 0), point!(-1, 0, 0), point!(1, 0, 0))
+    }
+
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let ray = ray.transform(self.inverse);
+
+        match intersect_barycentric(self.p1, self.e1, self.e2, &ray) {
+            Some((t, _, _)) => vec![Intersection::new(t, self)],
+            None => vec![],
+        }
+    }
+
+    fn transform(&self, by: Matrix<4, 4>) -> Self
+    where
+        Self: Sized,
+    {
+        let transformation = self.transformation * by;
+        let inverse = transformation.inverse();
+        Self {
+            transformation,
+            inverse,
+            inverse_transpose: inverse.transpose(),
+            ..self.clone()
+        }
+    }
+
+    fn normal_at(&self, _point: Tuple) -> Tuple {
+        let world_normal = self.inverse_transpose * self.normal;
+        // WORKAROUND! page 82
+        Tuple::new_vec(
+            world_normal[Position::X],
+            world_normal[Position::Y],
+            world_normal[Position::Z],
+        )
+        .normalize()
+    }
+
+    fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+
+    fn get_inverse_transpose(&self) -> Matrix<4, 4> {
+        self.inverse_transpose
+    }
+
+    fn get_bounds(&self) -> BoundingBox {
+        BoundingBox::empty()
+            .add_point(self.p1)
+            .add_point(self.p2)
+            .add_point(self.p3)
+    }
+
+    fn get_material(&self) -> Material {
+        self.material.clone()
+    }
+
+    fn set_material(&self, material: Material) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            material,
+            ..self.clone()
+        }
+    }
+}
+
+impl Triangle {
+    pub fn from_points(p1: Tuple, p2: Tuple, p3: Tuple) -> Self {
+        let e1 = p2 - p1;
+        let e2 = p3 - p1;
+
+        Self {
+            id: Uuid::new_v4(),
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            inverse_transpose: Matrix::identity_matrix(),
+            material: Material::default(),
+            p1,
+            p2,
+            p3,
+            e1,
+            e2,
+            normal: e2.cross(e1).normalize(),
+        }
+    }
+
+    pub fn get_points(&self) -> (Tuple, Tuple, Tuple) {
+        (self.p1, self.p2, self.p3)
+    }
+
+    pub fn get_edges(&self) -> (Tuple, Tuple) {
+        (self.e1, self.e2)
+    }
+
+    pub fn get_normal(&self) -> Tuple {
+        self.normal
+    }
+}
+
+/// Möller–Trumbore intersection of an object space ray with the triangle at `p1` spanned by
+/// the edges `e1` and `e2`.
+///
+/// Returns `(t, u, v)`, where `u` and `v` weigh the second and third corner respectively.
+pub(super) fn intersect_barycentric(
+    p1: Tuple,
+    e1: Tuple,
+    e2: Tuple,
+    ray: &Ray,
+) -> Option<(f64, f64, f64)> {
+    let dir_cross_e2 = ray.get_direction().cross(e2);
+    let det = e1.dot(dir_cross_e2);
+
+    // ray is parallel to the triangle
+    if det.abs() < EPSILON {
+        return None;
+    }
+
+    let f = 1f64 / det;
+    let p1_to_origin = ray.get_origin() - p1;
+    let u = f * p1_to_origin.dot(dir_cross_e2);
+    if !(0f64..=1f64).contains(&u) {
+        return None;
+    }
+
+    let origin_cross_e1 = p1_to_origin.cross(e1);
+    let v = f * ray.get_direction().dot(origin_cross_e1);
+    if v < 0f64 || u + v > 1f64 {
+        return None;
+    }
+
+    Some((f * e2.dot(origin_cross_e1), u, v))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        body::Body, material::Material, matrix::Matrix, point, ray::Ray, tuple::Tuple, vector,
+    };
+
+    use super::Triangle;
+
+    #[test]
+    fn constructing_triangle() {
+        let p1 = point!(0, 1, 0);
+        let p2 = point!(-1, 0, 0);
+        let p3 = point!(1, 0, 0);
+        let t = Triangle::from_points(p1, p2, p3);
+        assert_eq!(t.get_points(), (p1, p2, p3));
+        assert_eq!(t.get_edges(), (vector!(-1, -1, 0), vector!(1, -1, 0)));
+        assert_eq!(t.get_normal(), vector!(0, 0, -1))
+    }
+
+    #[test]
+    fn normal_on_triangle() {
+        let t = Triangle::new();
+        assert_eq!(t.normal_at(point!(0, 0.5, 0)), t.get_normal());
+        assert_eq!(t.normal_at(point!(-0.5, 0.75, 0)), t.get_normal());
+        assert_eq!(t.normal_at(point!(0.5, 0.25, 0)), t.get_normal())
+    }
+
+    #[test]
+    fn ray_parallel_to_triangle() {
+        let t = Triangle::new();
+        let r = Ray::new(point!(0, -1, -2), vector!(0, 1, 0));
+        assert!(t.intersect(&r).is_empty())
+    }
+
+    #[test]
+    fn ray_misses_triangle_edges() {
+        let t = Triangle::new();
+        let cases = [
+            (point!(1, 1, -2), vector!(0, 0, 1)),
+            (point!(-1, 1, -2), vector!(0, 0, 1)),
+            (point!(0, -1, -2), vector!(0, 0, 1)),
+        ];
+
+        for (origin, direction) in cases {
+            let r = Ray::new(origin, direction);
+            assert!(t.intersect(&r).is_empty())
+        }
+    }
+
+    #[test]
+    fn ray_strikes_triangle() {
+        let t = Triangle::new();
+        let r = Ray::new(point!(0, 0.5, -2), vector!(0, 0, 1));
+        let xs = t.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].get_t(), 2.0);
+        assert_eq!(xs[0].get_object().get_id(), t.get_id())
+    }
+
+    #[test]
+    fn normal_on_transformed_triangle() {
+        let t = Triangle::new().transform(Matrix::scaling_matrix(1.0, 1.0, -1.0));
+        assert_eq!(t.normal_at(point!(0, 0.5, 0)), vector!(0, 0, 1))
+    }
+
+    #[test]
+    fn triangle_may_be_assigned_material() {
+        let m = Material::default().set_ambient(1.0);
+        let t = Triangle::new().set_material(m.clone());
+        assert_eq!(t.get_material(), m)
+    }
+}
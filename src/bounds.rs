@@ -0,0 +1,299 @@
+use crate::{
+    matrix::Matrix,
+    point,
+    ray::Ray,
+    tuple::{Position, Tuple},
+    utils::EPSILON,
+};
+
+/// Axis-aligned bounding box, described by its minimum and maximum corner.
+///
+/// Components may be infinite for unbounded bodies like `Plane`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoundingBox {
+    min: Tuple,
+    max: Tuple,
+}
+
+impl BoundingBox {
+    pub fn new(min: Tuple, max: Tuple) -> Self {
+        Self { min, max }
+    }
+
+    /// Box containing nothing; adding a point or merging a box yields exactly that.
+    pub fn empty() -> Self {
+        Self {
+            min: point!(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+            max: point!(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+        }
+    }
+
+    pub fn infinite() -> Self {
+        Self {
+            min: point!(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+            max: point!(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+        }
+    }
+
+    pub fn get_min(&self) -> Tuple {
+        self.min
+    }
+
+    pub fn get_max(&self) -> Tuple {
+        self.max
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min[Position::X] > self.max[Position::X]
+            || self.min[Position::Y] > self.max[Position::Y]
+            || self.min[Position::Z] > self.max[Position::Z]
+    }
+
+    pub fn is_finite(&self) -> bool {
+        [
+            self.min[Position::X],
+            self.min[Position::Y],
+            self.min[Position::Z],
+            self.max[Position::X],
+            self.max[Position::Y],
+            self.max[Position::Z],
+        ]
+        .iter()
+        .all(|component| component.is_finite())
+    }
+
+    pub fn add_point(&self, point: Tuple) -> Self {
+        Self {
+            min: point!(
+                self.min[Position::X].min(point[Position::X]),
+                self.min[Position::Y].min(point[Position::Y]),
+                self.min[Position::Z].min(point[Position::Z])
+            ),
+            max: point!(
+                self.max[Position::X].max(point[Position::X]),
+                self.max[Position::Y].max(point[Position::Y]),
+                self.max[Position::Z].max(point[Position::Z])
+            ),
+        }
+    }
+
+    pub fn merge(&self, other: &BoundingBox) -> Self {
+        if other.is_empty() {
+            return *self;
+        }
+        self.add_point(other.min).add_point(other.max)
+    }
+
+    pub fn contains_point(&self, point: Tuple) -> bool {
+        (self.min[Position::X]..=self.max[Position::X]).contains(&point[Position::X])
+            && (self.min[Position::Y]..=self.max[Position::Y]).contains(&point[Position::Y])
+            && (self.min[Position::Z]..=self.max[Position::Z]).contains(&point[Position::Z])
+    }
+
+    pub fn centroid(&self) -> Tuple {
+        point!(
+            (self.min[Position::X] + self.max[Position::X]) / 2f64,
+            (self.min[Position::Y] + self.max[Position::Y]) / 2f64,
+            (self.min[Position::Z] + self.max[Position::Z]) / 2f64
+        )
+    }
+
+    pub fn surface_area(&self) -> f64 {
+        if self.is_empty() {
+            return 0f64;
+        }
+        let extent = self.max - self.min;
+        let (x, y, z) = (
+            extent[Position::X],
+            extent[Position::Y],
+            extent[Position::Z],
+        );
+        2f64 * (x * y + y * z + z * x)
+    }
+
+    /// Bounding box of this box after applying `transformation` to it.
+    ///
+    /// Unbounded boxes stay unbounded in every direction, as their corners cannot be
+    /// transformed reliably.
+    pub fn transform(&self, transformation: Matrix<4, 4>) -> Self {
+        if self.is_empty() {
+            return *self;
+        }
+        if !self.is_finite() {
+            return Self::infinite();
+        }
+
+        let (min, max) = (self.min, self.max);
+        let corners = [
+            min,
+            point!(min[Position::X], min[Position::Y], max[Position::Z]),
+            point!(min[Position::X], max[Position::Y], min[Position::Z]),
+            point!(min[Position::X], max[Position::Y], max[Position::Z]),
+            point!(max[Position::X], min[Position::Y], min[Position::Z]),
+            point!(max[Position::X], min[Position::Y], max[Position::Z]),
+            point!(max[Position::X], max[Position::Y], min[Position::Z]),
+            max,
+        ];
+
+        corners.iter().fold(Self::empty(), |bounds, corner| {
+            bounds.add_point(transformation * *corner)
+        })
+    }
+
+    /// Checks whether the ray passes through the box (slab method).
+    pub fn intersects(&self, ray: &Ray) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+
+        let origin = ray.get_origin();
+        let direction = ray.get_direction();
+        let (xtmin, xtmax) = Self::check_axis(
+            origin[Position::X],
+            direction[Position::X],
+            self.min[Position::X],
+            self.max[Position::X],
+        );
+        let (ytmin, ytmax) = Self::check_axis(
+            origin[Position::Y],
+            direction[Position::Y],
+            self.min[Position::Y],
+            self.max[Position::Y],
+        );
+        let (ztmin, ztmax) = Self::check_axis(
+            origin[Position::Z],
+            direction[Position::Z],
+            self.min[Position::Z],
+            self.max[Position::Z],
+        );
+
+        let tmin = xtmin.max(ytmin).max(ztmin);
+        let tmax = xtmax.min(ytmax).min(ztmax);
+        tmin <= tmax
+    }
+
+    fn check_axis(origin: f64, direction: f64, min: f64, max: f64) -> (f64, f64) {
+        if direction.abs() < EPSILON {
+            // parallel to the slab, either always or never inside of it
+            return if (min..=max).contains(&origin) {
+                (f64::NEG_INFINITY, f64::INFINITY)
+            } else {
+                (f64::INFINITY, f64::NEG_INFINITY)
+            };
+        }
+
+        let tmin = (min - origin) / direction;
+        let tmax = (max - origin) / direction;
+        if tmin > tmax {
+            (tmax, tmin)
+        } else {
+            (tmin, tmax)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::{
+        matrix::{transformation::Axis, Matrix},
+        point,
+        ray::Ray,
+        tuple::Tuple,
+        vector,
+    };
+
+    use super::BoundingBox;
+
+    #[test]
+    fn adding_points_to_empty_bounding_box() {
+        let b = BoundingBox::empty()
+            .add_point(point!(-5, 2, 0))
+            .add_point(point!(7, 0, -3));
+        assert_eq!(b.get_min(), point!(-5, 0, -3));
+        assert_eq!(b.get_max(), point!(7, 2, 0))
+    }
+
+    #[test]
+    fn merging_bounding_boxes() {
+        let b1 = BoundingBox::new(point!(-5, -2, 0), point!(7, 4, 4));
+        let b2 = BoundingBox::new(point!(8, -7, -2), point!(14, 2, 8));
+        let b = b1.merge(&b2);
+        assert_eq!(b.get_min(), point!(-5, -7, -2));
+        assert_eq!(b.get_max(), point!(14, 4, 8));
+        assert_eq!(b1.merge(&BoundingBox::empty()), b1)
+    }
+
+    #[test]
+    fn checking_if_box_contains_point() {
+        let b = BoundingBox::new(point!(5, -2, 0), point!(11, 4, 7));
+        assert!(b.contains_point(point!(5, -2, 0)));
+        assert!(b.contains_point(point!(8, 1, 3)));
+        assert!(!b.contains_point(point!(3, 0, 3)));
+        assert!(!b.contains_point(point!(8, -4, 3)));
+        assert!(!b.contains_point(point!(8, 1, 8)))
+    }
+
+    #[test]
+    fn transforming_bounding_box() {
+        let b = BoundingBox::new(point!(-1, -1, -1), point!(1, 1, 1));
+        let m = Matrix::rotation_matrix(Axis::X, PI / 4.0).rotate(Axis::Y, PI / 4.0);
+        let b = b.transform(m);
+        assert_eq!(b.get_min(), point!(-2f64.sqrt(), -1.70711, -1.70711));
+        assert_eq!(b.get_max(), point!(2f64.sqrt(), 1.70711, 1.70711))
+    }
+
+    #[test]
+    fn transforming_unbounded_box_stays_unbounded() {
+        let b = BoundingBox::new(
+            point!(f64::NEG_INFINITY, 0, f64::NEG_INFINITY),
+            point!(f64::INFINITY, 0, f64::INFINITY),
+        );
+        let b = b.transform(Matrix::translation_matrix(0.0, 1.0, 0.0));
+        assert!(!b.is_finite());
+        assert!(b.contains_point(point!(1000, -1000, 5)))
+    }
+
+    #[test]
+    fn surface_area_of_bounding_box() {
+        let b = BoundingBox::new(point!(0, 0, 0), point!(1, 2, 3));
+        assert_eq!(b.surface_area(), 22.0);
+        assert_eq!(BoundingBox::empty().surface_area(), 0.0)
+    }
+
+    #[test]
+    fn intersecting_ray_with_bounding_box() {
+        let b = BoundingBox::new(point!(5, -2, 0), point!(11, 4, 7));
+        let cases = [
+            (point!(15, 1, 2), vector!(-1, 0, 0), true),
+            (point!(-5, -1, 4), vector!(1, 0, 0), true),
+            (point!(7, 6, 5), vector!(0, -1, 0), true),
+            (point!(9, -5, 6), vector!(0, 1, 0), true),
+            (point!(8, 2, 12), vector!(0, 0, -1), true),
+            (point!(6, 0, -5), vector!(0, 0, 1), true),
+            (point!(8, 1, 3.5), vector!(0, 0, 1), true),
+            (point!(9, -1, -8), vector!(2, 4, 6), false),
+            (point!(8, 3, -4), vector!(6, 2, 4), false),
+            (point!(9, -1, -2), vector!(4, 6, 2), false),
+            (point!(4, 0, 9), vector!(0, 0, -1), false),
+            (point!(8, 6, -1), vector!(0, -1, 0), false),
+            (point!(12, 5, 4), vector!(-1, 0, 0), false),
+        ];
+
+        for (origin, direction, result) in cases {
+            let r = Ray::new(origin, direction.normalize());
+            assert_eq!(b.intersects(&r), result)
+        }
+    }
+
+    #[test]
+    fn intersecting_ray_with_unbounded_box() {
+        let b = BoundingBox::new(
+            point!(f64::NEG_INFINITY, 0, f64::NEG_INFINITY),
+            point!(f64::INFINITY, 0, f64::INFINITY),
+        );
+        assert!(b.intersects(&Ray::new(point!(0, 1, 0), vector!(0, -1, 0))));
+        assert!(!b.intersects(&Ray::new(point!(0, 1, 0), vector!(1, 0, 0))))
+    }
+}
@@ -0,0 +1,233 @@
+use crate::{
+    bounds::BoundingBox,
+    ray::Ray,
+    tuple::{Position, Tuple},
+};
+
+/// Maximum number of items a leaf holds before splitting is considered.
+const MAX_LEAF_SIZE: usize = 4;
+/// Cost of visiting a node relative to intersecting a single item.
+const TRAVERSAL_COST: f64 = 0.125;
+
+enum Node {
+    Leaf {
+        bounds: BoundingBox,
+        items: Vec<(usize, BoundingBox)>,
+    },
+    Interior {
+        bounds: BoundingBox,
+        left: Box<Node>,
+        right: Box<Node>,
+    },
+}
+
+/// Bounding volume hierarchy over a list of bounding boxes, split by the surface area
+/// heuristic.
+///
+/// The hierarchy only stores indices into the list it was built from. Items with unbounded
+/// boxes (e.g. planes) are kept outside of the tree and returned for every ray.
+pub struct Bvh {
+    root: Option<Node>,
+    unbounded: Vec<usize>,
+}
+
+impl Bvh {
+    pub fn build(bounds: &[BoundingBox]) -> Self {
+        let (bounded, unbounded): (Vec<usize>, Vec<usize>) =
+            (0..bounds.len()).partition(|&i| bounds[i].is_finite());
+
+        Self {
+            root: if bounded.is_empty() {
+                None
+            } else {
+                Some(Self::build_node(bounds, bounded))
+            },
+            unbounded,
+        }
+    }
+
+    /// Indices of all items the ray may intersect, in ascending order.
+    pub fn candidates(&self, ray: &Ray) -> Vec<usize> {
+        let mut candidates = self.unbounded.clone();
+
+        let mut stack = vec![];
+        if let Some(root) = &self.root {
+            stack.push(root);
+        }
+
+        while let Some(node) = stack.pop() {
+            match node {
+                Node::Leaf { bounds, items } => {
+                    if bounds.intersects(ray) {
+                        candidates.extend(
+                            items
+                                .iter()
+                                .filter(|(_, bounds)| bounds.intersects(ray))
+                                .map(|(index, _)| index),
+                        );
+                    }
+                }
+                Node::Interior {
+                    bounds,
+                    left,
+                    right,
+                } => {
+                    if bounds.intersects(ray) {
+                        stack.push(left);
+                        stack.push(right);
+                    }
+                }
+            }
+        }
+
+        // keeps intersection order identical to testing every item
+        candidates.sort_unstable();
+        candidates
+    }
+
+    fn build_node(bounds: &[BoundingBox], items: Vec<usize>) -> Node {
+        let node_bounds = items
+            .iter()
+            .fold(BoundingBox::empty(), |acc, &i| acc.merge(&bounds[i]));
+
+        if items.len() <= MAX_LEAF_SIZE {
+            return Self::leaf(bounds, node_bounds, items);
+        }
+
+        let (left, right) = match Self::split(bounds, &items, &node_bounds) {
+            Some(split) => split,
+            None => return Self::leaf(bounds, node_bounds, items),
+        };
+
+        Node::Interior {
+            bounds: node_bounds,
+            left: Box::new(Self::build_node(bounds, left)),
+            right: Box::new(Self::build_node(bounds, right)),
+        }
+    }
+
+    fn leaf(bounds: &[BoundingBox], node_bounds: BoundingBox, items: Vec<usize>) -> Node {
+        Node::Leaf {
+            bounds: node_bounds,
+            items: items.into_iter().map(|i| (i, bounds[i])).collect(),
+        }
+    }
+
+    /// Finds the cheapest split along any axis by sweeping over the items sorted by
+    /// centroid; `None` if keeping all items in one leaf is cheaper.
+    fn split(
+        bounds: &[BoundingBox],
+        items: &[usize],
+        node_bounds: &BoundingBox,
+    ) -> Option<(Vec<usize>, Vec<usize>)> {
+        let node_area = node_bounds.surface_area();
+        let count = items.len();
+        // (cost, axis, number of items going left)
+        let mut best: Option<(f64, usize, usize)> = None;
+
+        for axis in 0..3 {
+            let sorted = Self::sort_by_centroid(bounds, items, axis);
+
+            // surface area of the items right of index i, i.e. `sorted[i..]`
+            let mut right_areas = vec![0f64; count];
+            let mut right_bounds = BoundingBox::empty();
+            for i in (1..count).rev() {
+                right_bounds = right_bounds.merge(&bounds[sorted[i]]);
+                right_areas[i] = right_bounds.surface_area();
+            }
+
+            let mut left_bounds = BoundingBox::empty();
+            for i in 1..count {
+                left_bounds = left_bounds.merge(&bounds[sorted[i - 1]]);
+                let cost = if node_area > 0f64 {
+                    TRAVERSAL_COST
+                        + (left_bounds.surface_area() * i as f64
+                            + right_areas[i] * (count - i) as f64)
+                            / node_area
+                } else {
+                    // degenerated node without area, fall back to splitting in the middle
+                    TRAVERSAL_COST + (i as f64 - count as f64 / 2f64).abs()
+                };
+
+                if best.is_none_or(|(best_cost, _, _)| cost < best_cost) {
+                    best = Some((cost, axis, i));
+                }
+            }
+        }
+
+        let (cost, axis, index) = best?;
+        if node_area > 0f64 && cost >= count as f64 {
+            return None;
+        }
+
+        let mut left = Self::sort_by_centroid(bounds, items, axis);
+        let right = left.split_off(index);
+        Some((left, right))
+    }
+
+    fn sort_by_centroid(bounds: &[BoundingBox], items: &[usize], axis: usize) -> Vec<usize> {
+        let mut sorted = items.to_vec();
+        sorted.sort_by(|&a, &b| {
+            Self::component(bounds[a].centroid(), axis)
+                .total_cmp(&Self::component(bounds[b].centroid(), axis))
+        });
+        sorted
+    }
+
+    fn component(tuple: Tuple, axis: usize) -> f64 {
+        match axis {
+            0 => tuple[Position::X],
+            1 => tuple[Position::Y],
+            _ => tuple[Position::Z],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bounds::BoundingBox, point, ray::Ray, tuple::Tuple, vector};
+
+    use super::Bvh;
+
+    fn unit_box_at(x: f64) -> BoundingBox {
+        BoundingBox::new(point!(x - 0.5, -0.5, -0.5), point!(x + 0.5, 0.5, 0.5))
+    }
+
+    #[test]
+    fn empty_hierarchy_has_no_candidates() {
+        let bvh = Bvh::build(&[]);
+        let r = Ray::new(point!(0, 0, -5), vector!(0, 0, 1));
+        assert!(bvh.candidates(&r).is_empty())
+    }
+
+    #[test]
+    fn hierarchy_returns_only_boxes_hit_by_ray() {
+        let bounds: Vec<BoundingBox> = (0..100).map(|i| unit_box_at(i as f64 * 2.0)).collect();
+        let bvh = Bvh::build(&bounds);
+
+        let r = Ray::new(point!(20, 0, -5), vector!(0, 0, 1));
+        assert_eq!(bvh.candidates(&r), vec![10]);
+
+        let r = Ray::new(point!(-5, 0, 0), vector!(1, 0, 0));
+        assert_eq!(bvh.candidates(&r), (0..100).collect::<Vec<usize>>());
+
+        let r = Ray::new(point!(0, 5, -5), vector!(0, 0, 1));
+        assert!(bvh.candidates(&r).is_empty())
+    }
+
+    #[test]
+    fn unbounded_items_are_always_candidates() {
+        let bounds = vec![unit_box_at(0.0), BoundingBox::infinite(), unit_box_at(10.0)];
+        let bvh = Bvh::build(&bounds);
+        let r = Ray::new(point!(10, 0, -5), vector!(0, 0, 1));
+        assert_eq!(bvh.candidates(&r), vec![1, 2])
+    }
+
+    #[test]
+    fn identical_boxes_end_up_in_leaves() {
+        let bounds = vec![unit_box_at(0.0); 20];
+        let bvh = Bvh::build(&bounds);
+        let r = Ray::new(point!(0, 0, -5), vector!(0, 0, 1));
+        assert_eq!(bvh.candidates(&r).len(), 20)
+    }
+}
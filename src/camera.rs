@@ -1,4 +1,12 @@
-use crate::{canvas::Canvas, matrix::Matrix, point, ray::Ray, tuple::Tuple, world::World};
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use crate::{
+    canvas::Canvas, color::Color, matrix::Matrix, path_tracer::PathTracer, point, ray::Ray,
+    tuple::Tuple, world::World,
+};
 
 pub struct Camera {
     hsize: usize,
@@ -8,6 +16,7 @@ pub struct Camera {
     field_of_view: f64,
     pixel_size: f64,
     transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
 }
 
 impl Camera {
@@ -35,36 +44,105 @@ impl Camera {
             field_of_view,
             pixel_size,
             transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
         }
     }
 
     fn ray_for_pixel(&self, x: usize, y: usize) -> Ray {
-        let x_offset = (x as f64 + 0.5) * self.pixel_size;
-        let y_offset = (y as f64 + 0.5) * self.pixel_size;
+        self.ray_through(x as f64 + 0.5, y as f64 + 0.5)
+    }
+
+    /// Ray through a point on the canvas, e.g. `(x + 0.5, y + 0.5)` for the center of pixel
+    /// `(x, y)`.
+    pub(crate) fn ray_through(&self, x: f64, y: f64) -> Ray {
+        let x_offset = x * self.pixel_size;
+        let y_offset = y * self.pixel_size;
 
         let world_x = self.half_width - x_offset;
         let world_y = self.half_height - y_offset;
 
-        let inverse_transformation = self.transformation.inverse();
-        let pixel = inverse_transformation * point!(world_x, world_y, -1);
-        let origin = inverse_transformation * Tuple::point_origin();
+        let pixel = self.inverse * point!(world_x, world_y, -1);
+        let origin = self.inverse * Tuple::point_origin();
         let direction = (pixel - origin).normalize();
 
         Ray::new(origin, direction)
     }
 
     pub fn transform(&mut self, transformation: Matrix<4, 4>) -> &mut Self {
-        self.transformation = self.transformation * transformation;
-        self
+        self.set_transformation(self.transformation * transformation)
     }
 
+    /// Renders the world using one thread per available core.
     pub fn render(&self, world: &World) -> Canvas {
+        let threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+        self.render_with_threads(world, threads)
+    }
+
+    /// Renders the world using `threads` threads, which take scanlines from a shared queue.
+    ///
+    /// Every pixel is computed exactly as in the serial case, so the image does not depend on
+    /// the number of threads.
+    pub fn render_with_threads(&self, world: &World, threads: usize) -> Canvas {
+        self.render_pixels(threads, |x, y| world.color_at(self.ray_for_pixel(x, y)))
+    }
+
+    /// Renders the world by path tracing, using one thread per available core.
+    pub fn render_path_traced(&self, world: &World, tracer: &PathTracer) -> Canvas {
+        let threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+        self.render_path_traced_with_threads(world, tracer, threads)
+    }
+
+    /// Renders the world by path tracing, using `threads` threads. Samples are seeded per
+    /// pixel, so the image does not depend on the number of threads either.
+    pub fn render_path_traced_with_threads(
+        &self,
+        world: &World,
+        tracer: &PathTracer,
+        threads: usize,
+    ) -> Canvas {
+        self.render_pixels(threads, |x, y| tracer.pixel_color(world, self, x, y))
+    }
+
+    fn render_pixels<F>(&self, threads: usize, pixel: F) -> Canvas
+    where
+        F: Fn(usize, usize) -> Color + Sync,
+    {
         let mut image = Canvas::new(self.hsize, self.vsize);
 
-        for y in 0..self.vsize {
-            for x in 0..self.hsize {
-                let ray = self.ray_for_pixel(x, y);
-                let color = world.color_at(ray);
+        if threads <= 1 {
+            for y in 0..self.vsize {
+                for (x, color) in self.render_scanline(&pixel, y).into_iter().enumerate() {
+                    image.write_pixel(x, y, color);
+                }
+            }
+            return image;
+        }
+
+        let next_scanline = AtomicUsize::new(0);
+        let scanlines: Vec<(usize, Vec<Color>)> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads.min(self.vsize))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut rendered = vec![];
+                        loop {
+                            let y = next_scanline.fetch_add(1, Ordering::Relaxed);
+                            if y >= self.vsize {
+                                break rendered;
+                            }
+                            rendered.push((y, self.render_scanline(&pixel, y)));
+                        }
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("Render thread panicked."))
+                .collect()
+        });
+
+        for (y, colors) in scanlines {
+            for (x, color) in colors.into_iter().enumerate() {
                 image.write_pixel(x, y, color);
             }
         }
@@ -72,8 +150,13 @@ impl Camera {
         image
     }
 
+    fn render_scanline<F: Fn(usize, usize) -> Color>(&self, pixel: &F, y: usize) -> Vec<Color> {
+        (0..self.hsize).map(|x| pixel(x, y)).collect()
+    }
+
     pub fn set_transformation(&mut self, transformation: Matrix<4, 4>) -> &mut Self {
         self.transformation = transformation;
+        self.inverse = transformation.inverse();
         self
     }
 
@@ -96,6 +179,10 @@ impl Camera {
     pub fn get_transform(&self) -> Matrix<4, 4> {
         self.transformation
     }
+
+    pub fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
 }
 
 #[cfg(test)]
@@ -103,8 +190,11 @@ mod tests {
     use std::f64::consts::PI;
 
     use crate::{
+        body::{sphere::Sphere, Body},
         color::Color,
+        material::Material,
         matrix::{transformation::Axis, Matrix},
+        path_tracer::PathTracer,
         point,
         tuple::Tuple,
         utils::assert_f64_eq,
@@ -167,6 +257,17 @@ mod tests {
         )
     }
 
+    #[test]
+    fn transforming_camera_updates_inverse() {
+        let t = Matrix::rotation_matrix(Axis::Y, PI / 4.0).translate(0.0, -2.0, 5.0);
+        let mut c = Camera::new(201, 101, PI / 2.0);
+        assert_eq!(c.get_inverse(), Matrix::identity_matrix());
+        c.transform(t);
+        assert_eq!(c.get_inverse(), t.inverse());
+        c.set_transformation(Matrix::identity_matrix());
+        assert_eq!(c.get_inverse(), Matrix::identity_matrix())
+    }
+
     #[test]
     fn rendering_world_with_camera() {
         let w = World::default();
@@ -179,4 +280,60 @@ mod tests {
         let image = c.render(&w);
         assert_eq!(image.pixel_at(5, 5), Color::new(0.38066, 0.47583, 0.2855))
     }
+
+    #[test]
+    fn rendering_in_parallel_matches_serial_rendering() {
+        let mut w = World::default();
+        w.add_object(
+            Sphere::new()
+                .transform(Matrix::translation_matrix(1.5, 0.5, -0.5))
+                .set_material(Material::default().set_color(Color::new(0.1, 0.2, 0.9))),
+        );
+        let mut c = Camera::new(21, 21, PI / 3.0);
+        let c = c.transform(Matrix::view_transform_matrix(
+            point!(0, 1.5, -5),
+            point!(0, 1, 0),
+            vector!(0, 1, 0),
+        ));
+
+        let serial = c.render_with_threads(&w, 1);
+        for threads in [2, 4, 7] {
+            let parallel = c.render_with_threads(&w, threads);
+            for y in 0..21 {
+                for x in 0..21 {
+                    // compare exact bits, not within epsilon
+                    assert_eq!(
+                        format!("{:?}", serial.pixel_at(x, y)),
+                        format!("{:?}", parallel.pixel_at(x, y))
+                    )
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn path_traced_rendering_does_not_depend_on_threads() {
+        let w = World::default();
+        let mut c = Camera::new(9, 9, PI / 2.0);
+        let c = c.transform(Matrix::view_transform_matrix(
+            point!(0, 0, -5),
+            Tuple::point_origin(),
+            vector!(0, 1, 0),
+        ));
+        let tracer = PathTracer::new().set_samples(4);
+
+        let serial = c.render_path_traced_with_threads(&w, &tracer, 1);
+        let parallel = c.render_path_traced_with_threads(&w, &tracer, 3);
+        for y in 0..9 {
+            for x in 0..9 {
+                assert_eq!(
+                    format!("{:?}", serial.pixel_at(x, y)),
+                    format!("{:?}", parallel.pixel_at(x, y))
+                )
+            }
+        }
+        // the sphere is lit, its surroundings are not
+        assert_ne!(serial.pixel_at(4, 4), Color::black());
+        assert_eq!(serial.pixel_at(0, 0), Color::black())
+    }
 }
@@ -1,4 +1,8 @@
-use std::{fs::File, io::Write};
+use std::{
+    fs::{self, File},
+    io::{self, ErrorKind, Write},
+    path::Path,
+};
 
 use crate::color::Color;
 
@@ -17,6 +21,109 @@ impl Canvas {
         }
     }
 
+    /// Reads a plain (`P3`) or binary (`P6`) PPM image.
+    pub fn from_ppm(data: &[u8]) -> io::Result<Self> {
+        let invalid = |message: &str| io::Error::new(ErrorKind::InvalidData, message.to_string());
+        let mut position = 0;
+
+        let magic = Self::next_ppm_token(data, &mut position).ok_or(invalid("Missing header"))?;
+        let mut header = [0usize; 3];
+        for value in header.iter_mut() {
+            *value = Self::next_ppm_token(data, &mut position)
+                .and_then(|token| token.parse().ok())
+                .ok_or(invalid("Invalid header"))?;
+        }
+        let [width, height, max_value] = header;
+        if width == 0 || height == 0 {
+            return Err(invalid("Empty image"));
+        }
+        if !(1..=65535).contains(&max_value) {
+            return Err(invalid("Maximum color value out of range (1..=65535)"));
+        }
+        let sample_count = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(3))
+            .ok_or(invalid("Image too large"))?;
+
+        let samples: Vec<usize> = match magic.as_str() {
+            "P3" => {
+                // the header is not trusted to size the buffer
+                let mut samples = Vec::with_capacity(sample_count.min(data.len()));
+                while let Some(token) = Self::next_ppm_token(data, &mut position) {
+                    samples.push(token.parse().map_err(|_| invalid("Invalid pixel data"))?);
+                }
+                samples
+            }
+            "P6" => {
+                // exactly one whitespace separates header and binary data
+                let bytes = data.get(position + 1..).unwrap_or_default();
+                if max_value < 256 {
+                    bytes.iter().map(|&byte| byte as usize).collect()
+                } else {
+                    bytes
+                        .chunks_exact(2)
+                        .map(|pair| u16::from_be_bytes([pair[0], pair[1]]) as usize)
+                        .collect()
+                }
+            }
+            _ => return Err(invalid("Unsupported magic number, expected P3 or P6")),
+        };
+
+        if samples.len() < sample_count {
+            return Err(invalid("Missing pixel data"));
+        }
+
+        let mut canvas = Self::new(width, height);
+        for (index, rgb) in samples.chunks_exact(3).take(width * height).enumerate() {
+            let color = Color::new(
+                rgb[0] as f64 / max_value as f64,
+                rgb[1] as f64 / max_value as f64,
+                rgb[2] as f64 / max_value as f64,
+            );
+            canvas.write_pixel(index % width, index / width, color);
+        }
+
+        Ok(canvas)
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_ppm(&fs::read(path)?)
+    }
+
+    /// Next whitespace separated token of a PPM file, skipping comments.
+    fn next_ppm_token(data: &[u8], position: &mut usize) -> Option<String> {
+        loop {
+            while *position < data.len() && data[*position].is_ascii_whitespace() {
+                *position += 1;
+            }
+            if data.get(*position) != Some(&b'#') {
+                break;
+            }
+            while *position < data.len() && data[*position] != b'\n' {
+                *position += 1;
+            }
+        }
+
+        let start = *position;
+        while *position < data.len() && !data[*position].is_ascii_whitespace() {
+            *position += 1;
+        }
+
+        if start == *position {
+            None
+        } else {
+            Some(String::from_utf8_lossy(&data[start..*position]).into_owned())
+        }
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
     pub fn write_pixel(&mut self, x: usize, y: usize, color: Color) {
         self.validate_coordinates(x, y);
         self.pixels[y][x] = color;
@@ -68,10 +175,9 @@ impl Canvas {
         Ok(())
     }
 
-    // TESTME
     pub fn pixel_at(&self, x: usize, y: usize) -> Color {
         self.validate_coordinates(x, y);
-        self.pixels[x][y]
+        self.pixels[y][x]
     }
 }
 
@@ -151,4 +257,92 @@ mod tests {
             assert!(!(line.len() > 70))
         }
     }
+
+    #[test]
+    fn reading_pixel_at_coordinates() {
+        let mut canvas = Canvas::new(3, 2);
+        let red = Color::new(1.0, 0.0, 0.0);
+        canvas.write_pixel(2, 1, red);
+        assert_eq!(canvas.pixel_at(2, 1), red);
+        assert_eq!(canvas.pixel_at(1, 1), Color::black())
+    }
+
+    #[test]
+    fn reading_file_with_wrong_magic_number() {
+        let ppm = "P32\n1 1\n255\n0 0 0\n";
+        assert!(Canvas::from_ppm(ppm.as_bytes()).is_err())
+    }
+
+    #[test]
+    fn reading_plain_ppm_returns_canvas() {
+        let ppm = "P3\n10 2\n255\n\
+            0 0 0  1 1 1  2 2 2  3 3 3  4 4 4  5 5 5  6 6 6  7 7 7  8 8 8  9 9 9\n\
+            255 0 0  0 255 0  0 0 255  255 255 0  0 255 255  255 0 255  0 0 0  0 0 0  0 0 0  0 0 0\n";
+        let canvas = Canvas::from_ppm(ppm.as_bytes()).unwrap();
+        assert_eq!(canvas.get_width(), 10);
+        assert_eq!(canvas.get_height(), 2);
+        assert_eq!(canvas.pixel_at(0, 0), Color::black());
+        assert_eq!(canvas.pixel_at(9, 0), Color::new(0.03529, 0.03529, 0.03529));
+        assert_eq!(canvas.pixel_at(1, 1), Color::new(0.0, 1.0, 0.0));
+        assert_eq!(canvas.pixel_at(5, 1), Color::new(1.0, 0.0, 1.0))
+    }
+
+    #[test]
+    fn reading_plain_ppm_ignores_comments_and_line_breaks() {
+        let ppm = "P3\n# this is a comment\n2 1\n# this, too\n255\n\
+            51 153\n\n204\n# another comment\n255 255 255\n";
+        let canvas = Canvas::from_ppm(ppm.as_bytes()).unwrap();
+        assert_eq!(canvas.pixel_at(0, 0), Color::new(0.2, 0.6, 0.8));
+        assert_eq!(canvas.pixel_at(1, 0), Color::white())
+    }
+
+    #[test]
+    fn reading_ppm_respects_maximum_color_value() {
+        let ppm = "P3\n2 2\n100\n100 100 100  50 50 50\n75 50 25  0 0 0\n";
+        let canvas = Canvas::from_ppm(ppm.as_bytes()).unwrap();
+        assert_eq!(canvas.pixel_at(0, 1), Color::new(0.75, 0.5, 0.25))
+    }
+
+    #[test]
+    fn reading_binary_ppm_returns_canvas() {
+        let mut ppm = b"P6\n2 1\n255\n".to_vec();
+        ppm.extend_from_slice(&[255, 0, 0, 0, 51, 255]);
+        let canvas = Canvas::from_ppm(&ppm).unwrap();
+        assert_eq!(canvas.pixel_at(0, 0), Color::new(1.0, 0.0, 0.0));
+        assert_eq!(canvas.pixel_at(1, 0), Color::new(0.0, 0.2, 1.0));
+
+        let mut ppm = b"P6\n1 1\n65535\n".to_vec();
+        ppm.extend_from_slice(&[255, 255, 0, 0, 127, 255]);
+        let canvas = Canvas::from_ppm(&ppm).unwrap();
+        assert_eq!(canvas.pixel_at(0, 0), Color::new(1.0, 0.0, 0.5))
+    }
+
+    #[test]
+    fn reading_ppm_with_missing_pixels() {
+        let ppm = "P3\n2 2\n255\n0 0 0\n";
+        assert!(Canvas::from_ppm(ppm.as_bytes()).is_err())
+    }
+
+    #[test]
+    fn reading_ppm_with_invalid_dimensions() {
+        for ppm in [
+            "P3\n0 0\n255\n",
+            "P3\n0 2\n255\n",
+            "P3\n4000000000 4000000000\n255\n",
+            "P3\n100000 100000\n255\n0 0 0\n",
+            "P6\n100000 100000\n255\n\0\0\0",
+        ] {
+            let error = Canvas::from_ppm(ppm.as_bytes()).err().unwrap();
+            assert_eq!(error.kind(), ErrorKind::InvalidData)
+        }
+    }
+
+    #[test]
+    fn saved_ppm_can_be_read_back() {
+        let mut canvas = Canvas::new(4, 3);
+        canvas.write_pixel(3, 2, Color::new(1.0, 0.2, 0.6));
+        let read = Canvas::from_ppm(canvas.construct_ppm().as_bytes()).unwrap();
+        assert_eq!(read.pixel_at(3, 2), Color::new(1.0, 0.2, 0.6));
+        assert_eq!(read.pixel_at(0, 0), Color::black())
+    }
 }
@@ -74,6 +74,18 @@ impl Color {
         Self(1.0, 1.0, 1.0)
     }
 
+    pub fn get_red(&self) -> f64 {
+        self.0
+    }
+
+    pub fn get_green(&self) -> f64 {
+        self.1
+    }
+
+    pub fn get_blue(&self) -> f64 {
+        self.2
+    }
+
     pub fn as_8bit(&self) -> (u8, u8, u8) {
         (
             (self.0.clamp(0.0, 1.0) * 255.0).round() as u8,
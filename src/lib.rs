@@ -1,13 +1,21 @@
 #![cfg_attr(feature = "unstable", feature(generic_const_exprs))]
 #[allow(incomplete_features)]
 pub mod body;
+pub mod bounds;
+pub mod bvh;
 pub mod camera;
 pub mod canvas;
 pub mod color;
 pub mod light;
 pub mod material;
 pub mod matrix;
+pub mod noise;
+pub mod normal_perturbation;
+pub mod obj;
+pub mod path_tracer;
+pub mod pattern;
 pub mod ray;
+mod sampling;
 pub mod tuple;
 mod utils;
 pub mod world;
@@ -1,41 +1,103 @@
 use crate::{color::Color, tuple::Tuple};
 
-pub trait Light {
-    fn new(position: Tuple, intensity: Color) -> Self
-    where
-        Self: Sized;
+use self::attenuation::Attenuation;
+
+pub mod area_light;
+pub mod attenuation;
+pub mod directional_light;
+pub mod spot_light;
+
+/// Direction and distance from a shaded point towards the light, or a part of it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LightSample {
+    /// Normalized vector pointing towards the light.
+    pub direction: Tuple,
+    /// Infinite for lights without position.
+    pub distance: f64,
+}
+
+impl LightSample {
+    /// Sample of a light at `position` seen from `point`.
+    pub fn between(position: Tuple, point: Tuple) -> Self {
+        let v = position - point;
+        Self {
+            direction: v.normalize(),
+            distance: v.magnitude(),
+        }
+    }
+}
+
+pub trait Light: Send + Sync {
     fn get_intensity(&self) -> Color;
-    fn get_position(&self) -> Tuple;
+
+    /// Position of the light, `None` for lights infinitely far away.
+    fn get_position(&self) -> Option<Tuple>;
+
+    /// Samples of the light used for shading `point`.
+    fn get_samples(&self, point: Tuple) -> Vec<LightSample>;
+
+    /// Intensity of the light arriving at `point`, ignoring shadows. Constant by default.
+    fn intensity_towards(&self, _point: Tuple) -> Color {
+        self.get_intensity()
+    }
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct PointLight {
     intensity: Color,
     position: Tuple,
+    attenuation: Attenuation,
 }
 
 impl Light for PointLight {
-    fn new(position: Tuple, intensity: Color) -> Self {
+    fn get_intensity(&self) -> Color {
+        self.intensity
+    }
+
+    fn get_position(&self) -> Option<Tuple> {
+        Some(self.position)
+    }
+
+    fn get_samples(&self, point: Tuple) -> Vec<LightSample> {
+        vec![LightSample::between(self.position, point)]
+    }
+
+    fn intensity_towards(&self, point: Tuple) -> Color {
+        let distance = LightSample::between(self.position, point).distance;
+        self.intensity * self.attenuation.factor(distance)
+    }
+}
+
+impl PointLight {
+    pub fn new(position: Tuple, intensity: Color) -> Self {
         Self {
             intensity,
             position,
+            attenuation: Attenuation::none(),
         }
     }
 
-    fn get_intensity(&self) -> Color {
-        self.intensity
+    /// Lets the intensity decay with the distance from the light.
+    pub fn set_attenuation(&self, attenuation: Attenuation) -> Self {
+        Self {
+            attenuation,
+            ..self.clone()
+        }
     }
 
-    fn get_position(&self) -> Tuple {
-        self.position
+    pub fn get_attenuation(&self) -> Attenuation {
+        self.attenuation
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{color::Color, light::Light, tuple::Tuple};
+    use crate::{color::Color, material::Material, point, tuple::Tuple, vector, world::World};
 
-    use super::PointLight;
+    use super::{
+        attenuation::{Attenuation, Falloff},
+        Light, PointLight,
+    };
 
     #[test]
     fn point_light_has_position_and_intensity() {
@@ -45,4 +107,44 @@ mod tests {
         assert_eq!(light.intensity, intensity);
         assert_eq!(light.position, position)
     }
+
+    #[test]
+    fn point_light_evaluates_intensity_at_point() {
+        let w = World::default();
+        let light = w.get_lights()[0];
+        let cases = [
+            (point!(0, 1.0001, 0), 1.0),
+            (point!(-1.0001, 0, 0), 1.0),
+            (point!(0, 0, -1.0001), 1.0),
+            (point!(0, 0, 1.0001), 0.0),
+            (point!(1.0001, 0, 0), 0.0),
+            (point!(0, -1.0001, 0), 0.0),
+            (point!(0, 0, 0), 0.0),
+        ];
+        for (point, result) in cases {
+            assert_eq!(w.intensity_at(light, point), result)
+        }
+    }
+
+    #[test]
+    fn lighting_with_attenuated_point_light() {
+        let m = Material::default();
+        let light = PointLight::new(point!(0, 0, -2), Color::white())
+            .set_attenuation(Attenuation::new(Falloff::InverseSquare).set_range(5.0));
+        let eyev = vector!(0, 0, -1);
+        let normalv = vector!(0, 0, -1);
+        assert_eq!(
+            light.intensity_towards(point!(0, 0, 2)),
+            Color::new(0.0625, 0.0625, 0.0625)
+        );
+
+        let p = point!(0, 0, 0);
+        assert_eq!(
+            m.lighting(&light, p, p, eyev, normalv, 1.0),
+            Color::new(0.475, 0.475, 0.475)
+        );
+        // beyond the range not even ambient light arrives
+        let p = point!(0, 0, 4);
+        assert_eq!(m.lighting(&light, p, p, eyev, normalv, 1.0), Color::black())
+    }
 }
@@ -0,0 +1,265 @@
+use crate::{
+    color::Color,
+    sampling::Rng,
+    tuple::{Position, Tuple},
+};
+
+use super::{attenuation::Attenuation, Light, LightSample};
+
+/// Rectangular light emitting from a grid of cells, casting soft shadows.
+///
+/// Every cell is sampled once per shaded point. With jitter enabled the sample lies at a
+/// pseudo random position within the cell, derived from the shaded point so renders stay
+/// reproducible; otherwise the center of the cell is used.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AreaLight {
+    intensity: Color,
+    corner: Tuple,
+    // edges of a single cell
+    uvec: Tuple,
+    usteps: usize,
+    vvec: Tuple,
+    vsteps: usize,
+    jitter: bool,
+    attenuation: Attenuation,
+}
+
+impl Light for AreaLight {
+    fn get_intensity(&self) -> Color {
+        self.intensity
+    }
+
+    /// Center of the rectangle.
+    fn get_position(&self) -> Option<Tuple> {
+        Some(
+            self.corner
+                + self.uvec * (self.usteps as f64 / 2.0)
+                + self.vvec * (self.vsteps as f64 / 2.0),
+        )
+    }
+
+    fn get_samples(&self, point: Tuple) -> Vec<LightSample> {
+        (0..self.vsteps)
+            .flat_map(|v| (0..self.usteps).map(move |u| (u, v)))
+            .map(|(u, v)| LightSample::between(self.point_on_light(u, v, point), point))
+            .collect()
+    }
+
+    /// Attenuated by the distance from the center of the rectangle.
+    fn intensity_towards(&self, point: Tuple) -> Color {
+        let center = self.get_position().unwrap();
+        let distance = LightSample::between(center, point).distance;
+        self.intensity * self.attenuation.factor(distance)
+    }
+}
+
+impl AreaLight {
+    /// Rectangle spanned by `full_uvec` and `full_vvec` from `corner`, divided into
+    /// `usteps` times `vsteps` cells.
+    pub fn from_rectangle(
+        corner: Tuple,
+        full_uvec: Tuple,
+        usteps: usize,
+        full_vvec: Tuple,
+        vsteps: usize,
+        intensity: Color,
+    ) -> Self {
+        if usteps == 0 || vsteps == 0 {
+            panic!("Steps out of range (>=1)");
+        }
+
+        Self {
+            intensity,
+            corner,
+            uvec: full_uvec / usteps as f64,
+            usteps,
+            vvec: full_vvec / vsteps as f64,
+            vsteps,
+            jitter: true,
+            attenuation: Attenuation::none(),
+        }
+    }
+
+    /// Lets the intensity decay with the distance from the light.
+    pub fn set_attenuation(&self, attenuation: Attenuation) -> Self {
+        Self {
+            attenuation,
+            ..self.clone()
+        }
+    }
+
+    pub fn get_attenuation(&self) -> Attenuation {
+        self.attenuation
+    }
+
+    /// Disabling jitter samples the center of every cell, which may cause banding.
+    pub fn set_jitter(&self, jitter: bool) -> Self {
+        Self {
+            jitter,
+            ..self.clone()
+        }
+    }
+
+    pub fn get_corner(&self) -> Tuple {
+        self.corner
+    }
+
+    pub fn get_steps(&self) -> (usize, usize) {
+        (self.usteps, self.vsteps)
+    }
+
+    pub fn get_jitter(&self) -> bool {
+        self.jitter
+    }
+
+    /// Sample position within cell `(u, v)` when shading `point`.
+    fn point_on_light(&self, u: usize, v: usize, point: Tuple) -> Tuple {
+        let (ju, jv) = if self.jitter {
+            let cell = (v * self.usteps + u) as u64;
+            (
+                Self::jitter(point, 2 * cell),
+                Self::jitter(point, 2 * cell + 1),
+            )
+        } else {
+            (0.5, 0.5)
+        };
+
+        self.corner + self.uvec * (u as f64 + ju) + self.vvec * (v as f64 + jv)
+    }
+
+    /// Pseudo random value in `[0, 1)`, seeded by hashing the point and `seed`.
+    fn jitter(point: Tuple, seed: u64) -> f64 {
+        let mut rng = Rng::new(seed);
+        for coordinate in [Position::X, Position::Y, Position::Z] {
+            rng = Rng::new(rng.next_u64() ^ point[coordinate].to_bits());
+        }
+        rng.next_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        color::Color,
+        light::Light,
+        material::Material,
+        point,
+        tuple::{Position, Tuple},
+        vector,
+        world::World,
+    };
+
+    use super::AreaLight;
+
+    fn light() -> AreaLight {
+        AreaLight::from_rectangle(
+            point!(0, 0, 0),
+            vector!(2, 0, 0),
+            4,
+            vector!(0, 0, 1),
+            2,
+            Color::white(),
+        )
+    }
+
+    #[test]
+    fn creating_area_light() {
+        let light = light();
+        assert_eq!(light.get_corner(), point!(0, 0, 0));
+        assert_eq!(light.uvec, vector!(0.5, 0, 0));
+        assert_eq!(light.vvec, vector!(0, 0, 0.5));
+        assert_eq!(light.get_steps(), (4, 2));
+        assert_eq!(light.get_position(), Some(point!(1, 0, 0.5)));
+        assert!(light.get_jitter())
+    }
+
+    #[test]
+    fn finding_single_point_on_area_light() {
+        let light = light().set_jitter(false);
+        let cases = [
+            (0, 0, point!(0.25, 0, 0.25)),
+            (1, 0, point!(0.75, 0, 0.25)),
+            (0, 1, point!(0.25, 0, 0.75)),
+            (2, 0, point!(1.25, 0, 0.25)),
+            (3, 1, point!(1.75, 0, 0.75)),
+        ];
+        for (u, v, result) in cases {
+            assert_eq!(light.point_on_light(u, v, point!(0, 0, 0)), result)
+        }
+        assert_eq!(light.get_samples(point!(0, 0, 0)).len(), 8)
+    }
+
+    #[test]
+    fn jittered_points_stay_within_cells() {
+        let light = light();
+        for (u, v) in [(0, 0), (1, 0), (3, 0), (2, 1)] {
+            let sample = light.point_on_light(u, v, point!(1, 2, 3));
+            let (u, v) = (u as f64, v as f64);
+            assert!((u * 0.5..(u + 1.0) * 0.5).contains(&sample[Position::X]));
+            assert!((v * 0.5..(v + 1.0) * 0.5).contains(&sample[Position::Z]))
+        }
+        // same point, same samples
+        assert_eq!(
+            light.get_samples(point!(1, 2, 3)),
+            light.get_samples(point!(1, 2, 3))
+        );
+        assert_ne!(
+            light.get_samples(point!(1, 2, 3)),
+            light.get_samples(point!(1, 2, 4))
+        )
+    }
+
+    #[test]
+    fn area_light_intensity_function() {
+        let w = World::default();
+        let light = AreaLight::from_rectangle(
+            point!(-0.5, -0.5, -5),
+            vector!(1, 0, 0),
+            2,
+            vector!(0, 1, 0),
+            2,
+            Color::white(),
+        )
+        .set_jitter(false);
+        let cases = [
+            (point!(0, 0, 2), 0.0),
+            (point!(1, -1, 2), 0.25),
+            (point!(1.5, 0, 2), 0.5),
+            (point!(1.25, 1.25, 3), 0.75),
+            (point!(0, 0, -2), 1.0),
+        ];
+        for (point, result) in cases {
+            assert_eq!(w.intensity_at(&light, point), result)
+        }
+    }
+
+    #[test]
+    fn lighting_samples_area_light() {
+        let light = AreaLight::from_rectangle(
+            point!(-0.5, -0.5, -5),
+            vector!(1, 0, 0),
+            2,
+            vector!(0, 1, 0),
+            2,
+            Color::white(),
+        )
+        .set_jitter(false);
+        let m = Material::default()
+            .set_ambient(0.1)
+            .set_diffuse(0.9)
+            .set_specular(0.0);
+        let eye = point!(0, 0, -5);
+        let cases = [
+            (point!(0, 0, -1), Color::new(0.9965, 0.9965, 0.9965)),
+            (
+                point!(0, 2f64.sqrt() / 2.0, -2f64.sqrt() / 2.0),
+                Color::new(0.62318, 0.62318, 0.62318),
+            ),
+        ];
+        for (point, result) in cases {
+            let normalv = vector!(point[Position::X], point[Position::Y], point[Position::Z]);
+            let eyev = (eye - point).normalize();
+            assert_eq!(m.lighting(&light, point, point, eyev, normalv, 1.0), result)
+        }
+    }
+}
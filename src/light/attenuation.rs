@@ -0,0 +1,192 @@
+/// How the intensity of a light decays with the distance `d` from it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Falloff {
+    /// Constant intensity regardless of distance.
+    None,
+    /// Intensity scaled by `1 / d`, capped at `1` below a distance of `1`.
+    Linear,
+    /// Intensity scaled by `1 / d²`, physically correct for point lights; capped at `1` below
+    /// a distance of `1`.
+    InverseSquare,
+    /// Intensity scaled by `1 / (constant + linear * d + quadratic * d²)`, uncapped, so a
+    /// `constant` below `1` amplifies the light close to it. `constant` has to be positive.
+    Coefficients {
+        constant: f64,
+        linear: f64,
+        quadratic: f64,
+    },
+}
+
+/// Distance falloff of a positional light, with an optional range beyond which no light
+/// arrives.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Attenuation {
+    falloff: Falloff,
+    range: f64,
+}
+
+impl Attenuation {
+    /// No attenuation and unlimited range, the default of every light.
+    pub fn none() -> Self {
+        Self::new(Falloff::None)
+    }
+
+    pub fn new(falloff: Falloff) -> Self {
+        if let Falloff::Coefficients {
+            constant,
+            linear,
+            quadratic,
+        } = falloff
+        {
+            // also rejects NaN
+            if [constant, linear, quadratic]
+                .iter()
+                .any(|coefficient| !(0f64..).contains(coefficient))
+            {
+                panic!("Coefficients out of range (>=0)");
+            }
+            if constant == 0f64 {
+                panic!("Constant coefficient out of range (>0)");
+            }
+        }
+
+        Self {
+            falloff,
+            range: f64::INFINITY,
+        }
+    }
+
+    /// Cuts the light off beyond `range`.
+    pub fn set_range(&self, range: f64) -> Self {
+        if range.is_nan() || range <= 0f64 {
+            panic!("Range out of range (>0)");
+        }
+
+        Self { range, ..*self }
+    }
+
+    pub fn get_falloff(&self) -> Falloff {
+        self.falloff
+    }
+
+    pub fn get_range(&self) -> f64 {
+        self.range
+    }
+
+    /// Factor the intensity gets scaled by at `distance` from the light.
+    ///
+    /// `Linear` and `InverseSquare` never exceed `1`, so the intensity of the light is its
+    /// intensity at close range; they would otherwise grow without bound below a distance of
+    /// `1`, up to infinity at the light itself. `Coefficients` are used as given.
+    pub fn factor(&self, distance: f64) -> f64 {
+        if distance > self.range {
+            return 0f64;
+        }
+
+        match self.falloff {
+            Falloff::None => 1f64,
+            Falloff::Linear => 1f64 / distance.max(1f64),
+            Falloff::InverseSquare => 1f64 / distance.powi(2).max(1f64),
+            Falloff::Coefficients {
+                constant,
+                linear,
+                quadratic,
+            } => 1f64 / (constant + linear * distance + quadratic * distance.powi(2)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::assert_f64_eq;
+
+    use super::{Attenuation, Falloff};
+
+    #[test]
+    fn attenuation_by_falloff() {
+        let cases = [
+            (Falloff::None, 4.0, 1.0),
+            (Falloff::Linear, 4.0, 0.25),
+            (Falloff::InverseSquare, 4.0, 0.0625),
+            (
+                Falloff::Coefficients {
+                    constant: 1.0,
+                    linear: 0.5,
+                    quadratic: 0.25,
+                },
+                2.0,
+                1.0 / 3.0,
+            ),
+        ];
+        for (falloff, distance, result) in cases {
+            assert_f64_eq!(Attenuation::new(falloff).factor(distance), result)
+        }
+    }
+
+    #[test]
+    fn light_is_cut_off_beyond_range() {
+        let attenuation = Attenuation::new(Falloff::Linear).set_range(10.0);
+        assert_f64_eq!(attenuation.factor(10.0), 0.1);
+        assert_f64_eq!(attenuation.factor(10.5), 0.0);
+        assert_f64_eq!(Attenuation::none().set_range(3.0).factor(5.0), 0.0);
+        assert_eq!(Attenuation::none().get_range(), f64::INFINITY)
+    }
+
+    #[test]
+    fn attenuation_never_amplifies_light() {
+        for falloff in [Falloff::Linear, Falloff::InverseSquare] {
+            let attenuation = Attenuation::new(falloff);
+            assert_f64_eq!(attenuation.factor(0.0), 1.0);
+            assert_f64_eq!(attenuation.factor(0.5), 1.0);
+            assert_f64_eq!(attenuation.factor(1.0), 1.0)
+        }
+    }
+
+    #[test]
+    fn coefficients_are_not_capped() {
+        let attenuation = Attenuation::new(Falloff::Coefficients {
+            constant: 0.5,
+            linear: 0.0,
+            quadratic: 0.5,
+        });
+        assert_f64_eq!(attenuation.factor(0.0), 2.0);
+        assert_f64_eq!(attenuation.factor(1.0), 1.0);
+        assert_f64_eq!(attenuation.factor(3.0), 0.2)
+    }
+
+    #[test]
+    #[should_panic]
+    fn nan_coefficient() {
+        let _ = Attenuation::new(Falloff::Coefficients {
+            constant: 1.0,
+            linear: f64::NAN,
+            quadratic: 0.0,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn coefficients_all_zero() {
+        let _ = Attenuation::new(Falloff::Coefficients {
+            constant: 0.0,
+            linear: 0.0,
+            quadratic: 0.0,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_constant_coefficient() {
+        let _ = Attenuation::new(Falloff::Coefficients {
+            constant: 0.0,
+            linear: 1.0,
+            quadratic: 0.0,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative_range() {
+        let _ = Attenuation::none().set_range(-1.0);
+    }
+}
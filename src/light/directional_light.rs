@@ -0,0 +1,107 @@
+use crate::{color::Color, tuple::Tuple};
+
+use super::{Light, LightSample};
+
+/// Light infinitely far away, like the sun; all rays travel in the same direction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DirectionalLight {
+    intensity: Color,
+    direction: Tuple,
+}
+
+impl Light for DirectionalLight {
+    fn get_intensity(&self) -> Color {
+        self.intensity
+    }
+
+    fn get_position(&self) -> Option<Tuple> {
+        None
+    }
+
+    fn get_samples(&self, _point: Tuple) -> Vec<LightSample> {
+        vec![LightSample {
+            direction: -self.direction,
+            distance: f64::INFINITY,
+        }]
+    }
+}
+
+impl DirectionalLight {
+    /// `direction` is the direction the light travels in, e.g. down for the sun at noon.
+    pub fn new(direction: Tuple, intensity: Color) -> Self {
+        if direction.magnitude() == 0f64 {
+            panic!("Direction out of range (magnitude >0)");
+        }
+
+        Self {
+            intensity,
+            direction: direction.normalize(),
+        }
+    }
+
+    pub fn get_direction(&self) -> Tuple {
+        self.direction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        body::{plane::Plane, sphere::Sphere, Body},
+        color::Color,
+        light::Light,
+        material::Material,
+        matrix::Matrix,
+        point,
+        tuple::Tuple,
+        vector,
+        world::World,
+    };
+
+    use super::DirectionalLight;
+
+    #[test]
+    fn creating_directional_light() {
+        let light = DirectionalLight::new(vector!(0, -2, 0), Color::white());
+        assert_eq!(light.get_direction(), vector!(0, -1, 0));
+        assert_eq!(light.get_position(), None);
+
+        let samples = light.get_samples(point!(1, 2, 3));
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].direction, vector!(0, 1, 0));
+        assert_eq!(samples[0].distance, f64::INFINITY)
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_direction() {
+        let _ = DirectionalLight::new(vector!(0, 0, 0), Color::white());
+    }
+
+    #[test]
+    fn lighting_is_independent_of_position() {
+        let m = Material::default();
+        let light = DirectionalLight::new(vector!(0, 0, 1), Color::white());
+        let eyev = vector!(0, 0, -1);
+        let normalv = vector!(0, 0, -1);
+        for position in [point!(0, 0, 0), point!(100, -20, 1000)] {
+            assert_eq!(
+                m.lighting(&light, position, position, eyev, normalv, 1.0),
+                Color::new(1.9, 1.9, 1.9)
+            )
+        }
+    }
+
+    #[test]
+    fn shadow_rays_extend_to_infinity() {
+        let mut w = World::new();
+        w.add_object(Plane::new())
+            .add_object(Sphere::new().transform(Matrix::translation_matrix(0.0, 1000.0, 0.0)));
+        let light = DirectionalLight::new(vector!(0, -1, 0), Color::white());
+        // occluded by a sphere far above
+        assert_eq!(w.intensity_at(&light, point!(0, 0.0001, 0)), 0.0);
+        assert_eq!(w.intensity_at(&light, point!(5, 0.0001, 0)), 1.0);
+        // below the plane
+        assert_eq!(w.intensity_at(&light, point!(5, -1, 0)), 0.0)
+    }
+}
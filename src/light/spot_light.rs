@@ -0,0 +1,186 @@
+use std::f64::consts::PI;
+
+use crate::{color::Color, tuple::Tuple};
+
+use super::{attenuation::Attenuation, Light, LightSample};
+
+/// Light shining from a position into a cone around its direction.
+///
+/// Points within the inner angle receive the full intensity, which fades smoothly to zero
+/// towards the outer angle. Both angles are measured from the direction, in radians.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpotLight {
+    intensity: Color,
+    position: Tuple,
+    direction: Tuple,
+    inner_angle: f64,
+    outer_angle: f64,
+    attenuation: Attenuation,
+}
+
+impl Light for SpotLight {
+    fn get_intensity(&self) -> Color {
+        self.intensity
+    }
+
+    fn get_position(&self) -> Option<Tuple> {
+        Some(self.position)
+    }
+
+    fn get_samples(&self, point: Tuple) -> Vec<LightSample> {
+        vec![LightSample::between(self.position, point)]
+    }
+
+    fn intensity_towards(&self, point: Tuple) -> Color {
+        let sample = LightSample::between(self.position, point);
+        let cos = -sample.direction.dot(self.direction);
+        let (cos_outer, cos_inner) = (self.outer_angle.cos(), self.inner_angle.cos());
+
+        let falloff = if cos >= cos_inner {
+            1f64
+        } else if cos <= cos_outer {
+            0f64
+        } else {
+            // smoothstep
+            let x = (cos - cos_outer) / (cos_inner - cos_outer);
+            x * x * (3f64 - 2f64 * x)
+        };
+        self.intensity * falloff * self.attenuation.factor(sample.distance)
+    }
+}
+
+impl SpotLight {
+    /// Creates a spot light pointing down, with an inner angle of 30° and an outer one of 45°.
+    pub fn new(position: Tuple, intensity: Color) -> Self {
+        Self {
+            intensity,
+            position,
+            direction: Tuple::new_vec(0.0, -1.0, 0.0),
+            inner_angle: PI / 6.0,
+            outer_angle: PI / 4.0,
+            attenuation: Attenuation::none(),
+        }
+    }
+
+    /// Lets the intensity decay with the distance from the light.
+    pub fn set_attenuation(&self, attenuation: Attenuation) -> Self {
+        Self {
+            attenuation,
+            ..self.clone()
+        }
+    }
+
+    pub fn get_attenuation(&self) -> Attenuation {
+        self.attenuation
+    }
+
+    pub fn set_direction(&self, direction: Tuple) -> Self {
+        if direction.magnitude() == 0f64 {
+            panic!("Direction out of range (magnitude >0)");
+        }
+
+        Self {
+            direction: direction.normalize(),
+            ..self.clone()
+        }
+    }
+
+    pub fn set_angles(&self, inner_angle: f64, outer_angle: f64) -> Self {
+        if !(0f64..=PI).contains(&outer_angle) {
+            panic!("Outer angle out of range (0..=PI)");
+        }
+        if !(0f64..=outer_angle).contains(&inner_angle) {
+            panic!("Inner angle out of range (0..=outer angle)");
+        }
+
+        Self {
+            inner_angle,
+            outer_angle,
+            ..self.clone()
+        }
+    }
+
+    pub fn get_direction(&self) -> Tuple {
+        self.direction
+    }
+
+    pub fn get_angles(&self) -> (f64, f64) {
+        (self.inner_angle, self.outer_angle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::{color::Color, light::Light, material::Material, point, tuple::Tuple, vector};
+
+    use super::SpotLight;
+
+    fn spot_light() -> SpotLight {
+        SpotLight::new(point!(0, 0, 0), Color::white())
+            .set_direction(vector!(0, 0, 2))
+            .set_angles(PI / 6.0, PI / 3.0)
+    }
+
+    #[test]
+    fn creating_spot_light() {
+        let light = SpotLight::new(point!(0, 10, 0), Color::white());
+        assert_eq!(light.get_position(), Some(point!(0, 10, 0)));
+        assert_eq!(light.get_direction(), vector!(0, -1, 0));
+        assert_eq!(light.get_angles(), (PI / 6.0, PI / 4.0));
+        assert_eq!(spot_light().get_direction(), vector!(0, 0, 1))
+    }
+
+    #[test]
+    fn intensity_falls_off_between_angles() {
+        let light = spot_light();
+        // within the inner cone
+        assert_eq!(light.intensity_towards(point!(0, 0, 5)), Color::white());
+        assert_eq!(light.intensity_towards(point!(0, 0.5, 1)), Color::white());
+        // halfway between the cosines of both angles
+        let angle = ((PI / 6.0).cos() + (PI / 3.0).cos()) / 2.0;
+        let p = point!(0, angle.acos().tan(), 1);
+        assert_eq!(light.intensity_towards(p), Color::new(0.5, 0.5, 0.5));
+        // outside the outer cone and behind the light
+        assert_eq!(light.intensity_towards(point!(0, 2, 1)), Color::black());
+        assert_eq!(light.intensity_towards(point!(0, 0, -1)), Color::black())
+    }
+
+    #[test]
+    fn lighting_outside_of_cone() {
+        let m = Material::default();
+        let light =
+            SpotLight::new(point!(0, 0, -10), Color::white()).set_direction(vector!(0, 0, 1));
+        let eyev = vector!(0, 0, -1);
+        let normalv = vector!(0, 0, -1);
+        assert_eq!(
+            m.lighting(&light, point!(0, 0, 0), point!(0, 0, 0), eyev, normalv, 1.0),
+            Color::new(1.9, 1.9, 1.9)
+        );
+        // the ambient part is lost too, as no light arrives
+        assert_eq!(
+            m.lighting(
+                &light,
+                point!(0, 20, 0),
+                point!(0, 20, 0),
+                eyev,
+                normalv,
+                1.0
+            ),
+            Color::black()
+        )
+    }
+
+    #[test]
+    #[should_panic]
+    fn inner_angle_exceeding_outer_angle() {
+        let _ = SpotLight::new(point!(0, 0, 0), Color::white()).set_angles(PI / 3.0, PI / 6.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_direction() {
+        let _ = SpotLight::new(point!(0, 0, 0), Color::white()).set_direction(vector!(0, 0, 0));
+    }
+}
@@ -1,12 +1,39 @@
-use crate::{color::Color, light::Light, tuple::Tuple};
+use std::sync::Arc;
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+use crate::{
+    color::Color, light::Light, normal_perturbation::NormalPerturbation, pattern::Pattern,
+    tuple::Tuple,
+};
+
+use self::microfacet::Microfacet;
+
+pub mod microfacet;
+
+/// Refractive indices of common media.
+pub mod refractive_index {
+    pub const VACUUM: f64 = 1.0;
+    pub const AIR: f64 = 1.00029;
+    pub const WATER: f64 = 1.333;
+    pub const GLASS: f64 = 1.52;
+    pub const DIAMOND: f64 = 2.417;
+}
+
+#[derive(Clone, Debug)]
 pub struct Material {
     color: Color,
+    // replaces `color` if set
+    pattern: Option<Arc<dyn Pattern>>,
+    // applied to the normal of the body before shading
+    normal_perturbation: Option<Arc<dyn NormalPerturbation>>,
+    // replaces diffuse and specular Phong shading if set
+    microfacet: Option<Microfacet>,
     ambient: f64,
     diffuse: f64,
     specular: f64,
     shinyness: f64,
+    reflective: f64,
+    transparency: f64,
+    refractive_index: f64,
 }
 
 impl Material {
@@ -26,100 +53,248 @@ impl Material {
 
         Self {
             color,
+            pattern: None,
+            normal_perturbation: None,
+            microfacet: None,
             ambient,
             diffuse,
             specular,
             shinyness,
+            reflective: 0f64,
+            transparency: 0f64,
+            refractive_index: refractive_index::VACUUM,
         }
     }
 
     pub fn default() -> Self {
         Self {
             color: Color::new(1.0, 1.0, 1.0),
+            pattern: None,
+            normal_perturbation: None,
+            microfacet: None,
             ambient: 0.1,
             diffuse: 0.9,
             specular: 0.9,
             shinyness: 200.0,
+            reflective: 0.0,
+            transparency: 0.0,
+            refractive_index: refractive_index::VACUUM,
         }
     }
 
     // TODO: optimize
+    /// Phong shading of `position` lit by `light`.
+    ///
+    /// `intensity` is the fraction of the light reaching the position, `0` when fully in
+    /// shadow. Diffuse and specular parts are averaged over the samples of the light, and
+    /// computed by the microfacet model instead if the material has one.
     pub fn lighting(
         &self,
         light: &dyn Light,
         position: Tuple,
+        object_position: Tuple,
+        eyevector: Tuple,
+        normalvector: Tuple,
+        intensity: f64,
+    ) -> Color {
+        let color = self.color_at(object_position);
+        let ambient = color * light.intensity_towards(position) * self.ambient;
+        if intensity == 0f64 {
+            return ambient;
+        }
+
+        ambient
+            + self.diffuse_and_specular(light, color, position, eyevector, normalvector) * intensity
+    }
+
+    /// Like `lighting`, but without the ambient part; for integrators computing indirect
+    /// light themselves.
+    pub fn direct_lighting(
+        &self,
+        light: &dyn Light,
+        position: Tuple,
+        object_position: Tuple,
         eyevector: Tuple,
         normalvector: Tuple,
-        in_shadow: bool,
+        intensity: f64,
     ) -> Color {
-        let effective_color = self.color * light.get_intensity();
-        let lightv = (light.get_position() - position).normalize();
-        let ambient = effective_color * self.ambient;
-        let diffuse: Color;
-        let specular: Color;
-
-        // determine if light is behind surface
-        let light_dot_normal = lightv.dot(normalvector);
-        if light_dot_normal < 0.0 {
-            diffuse = Color::black();
-            specular = Color::black();
-        } else {
-            diffuse = effective_color * self.diffuse * light_dot_normal;
+        if intensity == 0f64 {
+            return Color::black();
+        }
+
+        let color = self.color_at(object_position);
+        self.diffuse_and_specular(light, color, position, eyevector, normalvector) * intensity
+    }
+
+    fn diffuse_and_specular(
+        &self,
+        light: &dyn Light,
+        color: Color,
+        position: Tuple,
+        eyevector: Tuple,
+        normalvector: Tuple,
+    ) -> Color {
+        let light_intensity = light.intensity_towards(position);
+        let effective_color = color * light_intensity;
+        let samples = light.get_samples(position);
+        let mut sum = Color::black();
+        for sample in &samples {
+            let lightv = sample.direction;
+
+            if let Some(microfacet) = &self.microfacet {
+                sum += microfacet.shade(color, light_intensity, normalvector, eyevector, lightv);
+                continue;
+            }
+
+            // determine if light is behind surface
+            let light_dot_normal = lightv.dot(normalvector);
+            if light_dot_normal < 0.0 {
+                continue;
+            }
+            sum += effective_color * self.diffuse * light_dot_normal;
 
             // determine if reflects towards eye
             let reflectv = -lightv.reflect_at(normalvector);
             let reflect_dot_eye = reflectv.dot(eyevector);
-            if reflect_dot_eye < 0.0 {
-                specular = Color::black()
-            } else {
+            if reflect_dot_eye > 0.0 {
                 let factor = reflect_dot_eye.powf(self.shinyness);
-                specular = light.get_intensity() * self.specular * factor
+                sum += light_intensity * self.specular * factor
             }
         }
 
-        if in_shadow {
-            ambient
-        } else {
-            ambient + diffuse + specular
-        }
+        sum * (1f64 / samples.len() as f64)
     }
 
     pub fn set_color(&self, color: Color) -> Self {
-        Self { color, ..*self }
+        Self {
+            color,
+            ..self.clone()
+        }
     }
 
     pub fn set_ambient(&self, ambient: f64) -> Self {
         if !(0f64..=1f64).contains(&ambient) {
             panic!("Ambient out of range (0..=1)");
         }
-        Self { ambient, ..*self }
+        Self {
+            ambient,
+            ..self.clone()
+        }
     }
 
     pub fn set_diffuse(&self, diffuse: f64) -> Self {
         if !(0f64..=1f64).contains(&diffuse) {
             panic!("Diffuse out of range (0..=1)");
         }
-        Self { diffuse, ..*self }
+        Self {
+            diffuse,
+            ..self.clone()
+        }
     }
 
     pub fn set_specular(&self, specular: f64) -> Self {
         if !(0f64..=1f64).contains(&specular) {
             panic!("Specular out of range (0..=1)");
         }
-        Self { specular, ..*self }
+        Self {
+            specular,
+            ..self.clone()
+        }
     }
 
     pub fn set_shinyness(&self, shinyness: f64) -> Self {
         if !(10f64 <= shinyness) {
             panic!("Shinyness out of range (>=10)");
         }
-        Self { shinyness, ..*self }
+        Self {
+            shinyness,
+            ..self.clone()
+        }
+    }
+
+    /// Share of the color contributed by reflections; `0` for matte, `1` for a perfect mirror.
+    pub fn set_reflective(&self, reflective: f64) -> Self {
+        if !(0f64..=1f64).contains(&reflective) {
+            panic!("Reflective out of range (0..=1)");
+        }
+        Self {
+            reflective,
+            ..self.clone()
+        }
+    }
+
+    /// Share of the color contributed by light passing through the material.
+    pub fn set_transparency(&self, transparency: f64) -> Self {
+        if !(0f64..=1f64).contains(&transparency) {
+            panic!("Transparency out of range (0..=1)");
+        }
+        Self {
+            transparency,
+            ..self.clone()
+        }
+    }
+
+    /// See `refractive_index` for common values.
+    pub fn set_refractive_index(&self, refractive_index: f64) -> Self {
+        if !(1f64..).contains(&refractive_index) {
+            panic!("Refractive index out of range (>=1)");
+        }
+        Self {
+            refractive_index,
+            ..self.clone()
+        }
+    }
+
+    pub fn set_pattern<P: Pattern + 'static>(&self, pattern: P) -> Self {
+        Self {
+            pattern: Some(Arc::new(pattern)),
+            ..self.clone()
+        }
+    }
+
+    pub fn set_normal_perturbation<N: NormalPerturbation + 'static>(
+        &self,
+        normal_perturbation: N,
+    ) -> Self {
+        Self {
+            normal_perturbation: Some(Arc::new(normal_perturbation)),
+            ..self.clone()
+        }
+    }
+
+    /// Shades the material physically based, using its color as base color. Ambient, reflective
+    /// and transparency still apply; diffuse, specular and shinyness are ignored.
+    pub fn set_microfacet(&self, microfacet: Microfacet) -> Self {
+        Self {
+            microfacet: Some(microfacet),
+            ..self.clone()
+        }
     }
 
     pub fn get_color(&self) -> Color {
         self.color
     }
 
+    pub fn get_pattern(&self) -> Option<&dyn Pattern> {
+        self.pattern.as_deref()
+    }
+
+    pub fn get_normal_perturbation(&self) -> Option<&dyn NormalPerturbation> {
+        self.normal_perturbation.as_deref()
+    }
+
+    pub fn get_microfacet(&self) -> Option<Microfacet> {
+        self.microfacet
+    }
+
+    /// Color of the material at a point in object space, taking the pattern into account.
+    pub fn color_at(&self, object_position: Tuple) -> Color {
+        match &self.pattern {
+            Some(pattern) => pattern.color_at_object(object_position),
+            None => self.color,
+        }
+    }
+
     pub fn get_ambient(&self) -> f64 {
         self.ambient
     }
@@ -135,19 +310,63 @@ impl Material {
     pub fn get_shinyness(&self) -> f64 {
         self.shinyness
     }
+
+    pub fn get_reflective(&self) -> f64 {
+        self.reflective
+    }
+
+    pub fn get_transparency(&self) -> f64 {
+        self.transparency
+    }
+
+    pub fn get_refractive_index(&self) -> f64 {
+        self.refractive_index
+    }
+}
+
+impl PartialEq for Material {
+    fn eq(&self, other: &Self) -> bool {
+        // patterns are only equal if shared
+        let same_pattern = match (&self.pattern, &other.pattern) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+
+        let same_normal_perturbation = match (&self.normal_perturbation, &other.normal_perturbation)
+        {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+
+        same_pattern
+            && same_normal_perturbation
+            && self.microfacet == other.microfacet
+            && self.color == other.color
+            && self.ambient == other.ambient
+            && self.diffuse == other.diffuse
+            && self.specular == other.specular
+            && self.shinyness == other.shinyness
+            && self.reflective == other.reflective
+            && self.transparency == other.transparency
+            && self.refractive_index == other.refractive_index
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
         color::Color,
-        light::{Light, PointLight},
+        light::PointLight,
+        matrix::Matrix,
+        pattern::{stripe::Stripe, Pattern},
         point,
         tuple::Tuple,
         vector,
     };
 
-    use super::Material;
+    use super::{microfacet::Microfacet, Material};
 
     #[test]
     fn default_material() {
@@ -156,7 +375,22 @@ mod tests {
         assert_eq!(m.get_ambient(), 0.1);
         assert_eq!(m.get_diffuse(), 0.9);
         assert_eq!(m.get_specular(), 0.9);
-        assert_eq!(m.get_shinyness(), 200.0)
+        assert_eq!(m.get_shinyness(), 200.0);
+        assert_eq!(m.get_reflective(), 0.0);
+        assert_eq!(m.get_transparency(), 0.0);
+        assert_eq!(m.get_refractive_index(), 1.0)
+    }
+
+    #[test]
+    #[should_panic]
+    fn refractive_index_below_vacuum() {
+        let _ = Material::default().set_refractive_index(0.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn reflective_out_of_range() {
+        let _ = Material::default().set_reflective(1.5);
     }
 
     #[test]
@@ -166,7 +400,7 @@ mod tests {
         let eyev = Tuple::new_vec(0.0, 0.0, -1.0);
         let normalv = eyev.clone();
         let light = PointLight::new(Tuple::new_point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
-        let result = m.lighting(&light, position, eyev, normalv, false);
+        let result = m.lighting(&light, position, position, eyev, normalv, 1.0);
         assert_eq!(result, Color::new(1.9, 1.9, 1.9))
     }
 
@@ -177,7 +411,7 @@ mod tests {
         let eyev = Tuple::new_vec(0.0, 2f64.sqrt() / 2.0, -2f64.sqrt() / 2.0);
         let normalv = Tuple::new_vec(0.0, 0.0, -1.0);
         let light = PointLight::new(Tuple::new_point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
-        let result = m.lighting(&light, position, eyev, normalv, false);
+        let result = m.lighting(&light, position, position, eyev, normalv, 1.0);
         assert_eq!(result, Color::new(1.0, 1.0, 1.0))
     }
 
@@ -191,7 +425,7 @@ mod tests {
             Tuple::new_point(0.0, 10.0, -10.0),
             Color::new(1.0, 1.0, 1.0),
         );
-        let result = m.lighting(&light, position, eyev, normalv, false);
+        let result = m.lighting(&light, position, position, eyev, normalv, 1.0);
         assert_eq!(result, Color::new(0.7364, 0.7364, 0.7364))
     }
 
@@ -205,7 +439,7 @@ mod tests {
             Tuple::new_point(0.0, 10.0, -10.0),
             Color::new(1.0, 1.0, 1.0),
         );
-        let result = m.lighting(&light, position, eyev, normalv, false);
+        let result = m.lighting(&light, position, position, eyev, normalv, 1.0);
         assert_eq!(result, Color::new(1.6364, 1.6364, 1.6364))
     }
 
@@ -216,7 +450,7 @@ mod tests {
         let eyev = Tuple::new_vec(0.0, 0.0, -1.0);
         let normalv = eyev.clone();
         let light = PointLight::new(Tuple::new_point(0.0, 0.0, 10.0), Color::new(1.0, 1.0, 1.0));
-        let result = m.lighting(&light, position, eyev, normalv, false);
+        let result = m.lighting(&light, position, position, eyev, normalv, 1.0);
         assert_eq!(result, Color::new(0.1, 0.1, 0.1))
     }
 
@@ -227,7 +461,77 @@ mod tests {
         let eyev = vector!(0, 0, -1);
         let normalv = eyev.clone();
         let light = PointLight::new(point!(0, 0, -10), Color::white());
-        let result = m.lighting(&light, position, eyev, normalv, true);
+        let result = m.lighting(&light, position, position, eyev, normalv, 0.0);
         assert_eq!(result, Color::new(0.1, 0.1, 0.1))
     }
+
+    #[test]
+    fn lighting_with_pattern_applied() {
+        let m = Material::default()
+            .set_pattern(Stripe::new(Color::white(), Color::black()))
+            .set_ambient(1.0)
+            .set_diffuse(0.0)
+            .set_specular(0.0);
+        let eyev = vector!(0, 0, -1);
+        let normalv = vector!(0, 0, -1);
+        let light = PointLight::new(point!(0, 0, -10), Color::white());
+        let p1 = point!(0.9, 0, 0);
+        let p2 = point!(1.1, 0, 0);
+        assert_eq!(
+            m.lighting(&light, p1, p1, eyev, normalv, 1.0),
+            Color::white()
+        );
+        assert_eq!(
+            m.lighting(&light, p2, p2, eyev, normalv, 1.0),
+            Color::black()
+        )
+    }
+
+    #[test]
+    fn material_color_uses_pattern_transformation() {
+        let m = Material::default().set_pattern(
+            Stripe::new(Color::white(), Color::black())
+                .transform(Matrix::scaling_matrix(2.0, 2.0, 2.0)),
+        );
+        assert_eq!(m.color_at(point!(1.5, 0, 0)), Color::white());
+        assert_eq!(m.color_at(point!(2.5, 0, 0)), Color::black());
+        assert_eq!(
+            Material::default().color_at(point!(2.5, 0, 0)),
+            Color::white()
+        )
+    }
+
+    #[test]
+    fn lighting_with_microfacet_model() {
+        let microfacet = Microfacet::new(0.0, 0.5);
+        let m = Material::default()
+            .set_color(Color::new(0.8, 0.2, 0.2))
+            .set_microfacet(microfacet);
+        assert_eq!(m.get_microfacet(), Some(microfacet));
+
+        let position = Tuple::point_origin();
+        let eyev = vector!(0, 0, -1);
+        let normalv = vector!(0, 0, -1);
+        let light = PointLight::new(point!(0, 10, -10), Color::white());
+        let lightv = vector!(0, 1, -1).normalize();
+        let direct = microfacet.shade(
+            Color::new(0.8, 0.2, 0.2),
+            Color::white(),
+            normalv,
+            eyev,
+            lightv,
+        );
+        assert_eq!(
+            m.lighting(&light, position, position, eyev, normalv, 1.0),
+            Color::new(0.08, 0.02, 0.02) + direct
+        );
+        assert_eq!(
+            m.direct_lighting(&light, position, position, eyev, normalv, 1.0),
+            direct
+        );
+        assert_eq!(
+            m.lighting(&light, position, position, eyev, normalv, 0.0),
+            Color::new(0.08, 0.02, 0.02)
+        )
+    }
 }
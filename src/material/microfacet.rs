@@ -0,0 +1,247 @@
+use std::f64::consts::PI;
+
+use crate::{
+    color::Color,
+    sampling::{self, Rng},
+    tuple::Tuple,
+};
+
+/// Reflectance of dielectrics at normal incidence, i.e. of non metallic materials.
+const DIELECTRIC_REFLECTANCE: f64 = 0.04;
+/// Lower bound of the GGX alpha, avoids the singular distribution of perfect mirrors.
+const MIN_ALPHA: f64 = 0.001;
+/// Lower bound of cosines in denominators, avoids dividing by zero at grazing angles.
+const MIN_COS: f64 = 0.001;
+
+/// Physically based Cook-Torrance microfacet model with metallic/roughness parameters, using
+/// the GGX distribution, the Smith geometry term and Schlick's Fresnel approximation.
+///
+/// The base color is taken from the color or pattern of the `Material`. Light intensities are
+/// scaled by π, so a rough white dielectric is about as bright as the default Phong diffuse.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Microfacet {
+    metallic: f64,
+    roughness: f64,
+}
+
+impl Microfacet {
+    pub fn new(metallic: f64, roughness: f64) -> Self {
+        if !(0f64..=1f64).contains(&metallic) {
+            panic!("Metallic out of range (0..=1)");
+        }
+        if !(0f64..=1f64).contains(&roughness) {
+            panic!("Roughness out of range (0..=1)");
+        }
+
+        Self {
+            metallic,
+            roughness,
+        }
+    }
+
+    pub fn get_metallic(&self) -> f64 {
+        self.metallic
+    }
+
+    pub fn get_roughness(&self) -> f64 {
+        self.roughness
+    }
+
+    /// Reflectance at normal incidence; metals tint their reflections by the base color.
+    pub fn f0(&self, base_color: Color) -> Color {
+        let dielectric = Color::new(
+            DIELECTRIC_REFLECTANCE,
+            DIELECTRIC_REFLECTANCE,
+            DIELECTRIC_REFLECTANCE,
+        );
+        dielectric * (1f64 - self.metallic) + base_color * self.metallic
+    }
+
+    /// Value of the BRDF for light arriving from `lightv` and leaving towards `eyev`, all
+    /// vectors normalized and pointing away from the surface.
+    pub fn brdf(&self, base_color: Color, normalv: Tuple, eyev: Tuple, lightv: Tuple) -> Color {
+        let n_dot_l = normalv.dot(lightv);
+        let n_dot_v = normalv.dot(eyev).max(MIN_COS);
+        if n_dot_l <= 0f64 {
+            return Color::black();
+        }
+
+        let halfv = (eyev + lightv).normalize();
+        let alpha = self.alpha();
+        let fresnel = self.fresnel(base_color, eyev.dot(halfv));
+        let specular = fresnel
+            * (Self::distribution(alpha, normalv.dot(halfv))
+                * Self::geometry(alpha, n_dot_l)
+                * Self::geometry(alpha, n_dot_v)
+                / (4f64 * n_dot_l * n_dot_v));
+        // light not reflected at the surface is scattered diffusely, except by metals
+        let diffuse = (Color::white() - fresnel) * base_color * ((1f64 - self.metallic) / PI);
+
+        diffuse + specular
+    }
+
+    /// Direct light reflected towards `eyev` by a light of `intensity` in direction `lightv`.
+    pub fn shade(
+        &self,
+        base_color: Color,
+        intensity: Color,
+        normalv: Tuple,
+        eyev: Tuple,
+        lightv: Tuple,
+    ) -> Color {
+        let n_dot_l = normalv.dot(lightv).max(0f64);
+        self.brdf(base_color, normalv, eyev, lightv) * intensity * (PI * n_dot_l)
+    }
+
+    /// Samples a direction to continue a path in, by importance of either the specular or the
+    /// diffuse lobe. Returns the direction together with the BRDF times cosine divided by the
+    /// probability density, or `None` if the path is absorbed.
+    pub(crate) fn sample(
+        &self,
+        base_color: Color,
+        normalv: Tuple,
+        eyev: Tuple,
+        rng: &mut Rng,
+    ) -> Option<(Tuple, Color)> {
+        let alpha = self.alpha();
+        let specular_probability = self.specular_probability(base_color);
+
+        let lightv = if rng.next_f64() < specular_probability {
+            let halfv = sampling::ggx_half_vector(normalv, alpha, rng);
+            -eyev.reflect_at(halfv)
+        } else {
+            sampling::cosine_hemisphere(normalv, rng)
+        };
+        let n_dot_l = normalv.dot(lightv);
+        if n_dot_l <= 0f64 {
+            return None;
+        }
+
+        // density of the mixture of both lobes
+        let halfv = (eyev + lightv).normalize();
+        let n_dot_h = normalv.dot(halfv).max(0f64);
+        let specular_pdf = Self::distribution(alpha, n_dot_h) * n_dot_h
+            / (4f64 * eyev.dot(halfv).abs().max(MIN_COS));
+        let diffuse_pdf = n_dot_l / PI;
+        let pdf = specular_probability * specular_pdf + (1f64 - specular_probability) * diffuse_pdf;
+
+        let weight = self.brdf(base_color, normalv, eyev, lightv) * (n_dot_l / pdf);
+        Some((lightv, weight))
+    }
+
+    fn alpha(&self) -> f64 {
+        self.roughness.powi(2).max(MIN_ALPHA)
+    }
+
+    /// Share of paths sampling the specular lobe, higher for metals.
+    fn specular_probability(&self, base_color: Color) -> f64 {
+        let f0 = self.f0(base_color);
+        let f0 = (f0.get_red() + f0.get_green() + f0.get_blue()) / 3f64;
+        (f0 + 0.25).clamp(0.25, 0.9)
+    }
+
+    fn fresnel(&self, base_color: Color, cos: f64) -> Color {
+        let f0 = self.f0(base_color);
+        f0 + (Color::white() - f0) * (1f64 - cos.clamp(0f64, 1f64)).powi(5)
+    }
+
+    /// GGX normal distribution.
+    fn distribution(alpha: f64, n_dot_h: f64) -> f64 {
+        let alpha2 = alpha.powi(2);
+        alpha2 / (PI * (n_dot_h.powi(2) * (alpha2 - 1f64) + 1f64).powi(2))
+    }
+
+    /// Smith masking term of GGX for one direction.
+    fn geometry(alpha: f64, n_dot_x: f64) -> f64 {
+        let alpha2 = alpha.powi(2);
+        2f64 * n_dot_x / (n_dot_x + (alpha2 + (1f64 - alpha2) * n_dot_x.powi(2)).sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::{
+        color::Color,
+        sampling::{self, Rng},
+        tuple::Tuple,
+        utils::float_eq,
+        vector,
+    };
+
+    use super::Microfacet;
+
+    #[test]
+    fn reflectance_at_normal_incidence() {
+        let base = Color::new(1.0, 0.5, 0.2);
+        assert_eq!(
+            Microfacet::new(0.0, 0.5).f0(base),
+            Color::new(0.04, 0.04, 0.04)
+        );
+        assert_eq!(Microfacet::new(1.0, 0.5).f0(base), base)
+    }
+
+    #[test]
+    #[should_panic]
+    fn roughness_out_of_range() {
+        let _ = Microfacet::new(0.0, 1.5);
+    }
+
+    #[test]
+    fn brdf_is_reciprocal() {
+        let m = Microfacet::new(0.3, 0.4);
+        let base = Color::new(0.8, 0.6, 0.4);
+        let n = vector!(0, 1, 0);
+        let v = vector!(0.3, 1, -0.2).normalize();
+        let l = vector!(-0.5, 0.8, 0.4).normalize();
+        assert_eq!(m.brdf(base, n, v, l), m.brdf(base, n, l, v));
+        assert_eq!(m.brdf(base, n, v, -l), Color::black())
+    }
+
+    #[test]
+    fn smoother_surfaces_have_brighter_highlights() {
+        let base = Color::white();
+        let n = vector!(0, 0, -1);
+        let v = vector!(0, 1, -1).normalize();
+        let mirrored = vector!(0, -1, -1).normalize();
+        let rough = Microfacet::new(0.0, 0.8).shade(base, Color::white(), n, v, mirrored);
+        let smooth = Microfacet::new(0.0, 0.2).shade(base, Color::white(), n, v, mirrored);
+        assert!(smooth.get_red() > rough.get_red() * 2.0)
+    }
+
+    #[test]
+    fn importance_sampling_matches_uniform_estimate() {
+        // both estimate the share of light reflected towards the eye
+        let base = Color::new(0.9, 0.6, 0.3);
+        let n = vector!(0, 1, 0);
+        let v = vector!(0.5, 1, 0).normalize();
+        let count = 40000;
+
+        for m in [Microfacet::new(0.0, 0.9), Microfacet::new(1.0, 0.5)] {
+            let mut rng = Rng::new(3);
+            let mut importance = Color::black();
+            let mut cosine = Color::black();
+            for _ in 0..count {
+                if let Some((_, weight)) = m.sample(base, n, v, &mut rng) {
+                    importance += weight * (1.0 / count as f64);
+                }
+                let l = sampling::cosine_hemisphere(n, &mut rng);
+                cosine += m.brdf(base, n, v, l) * (PI / count as f64);
+            }
+            assert!((importance.get_red() - cosine.get_red()).abs() < 0.03);
+            assert!((importance.get_blue() - cosine.get_blue()).abs() < 0.03);
+            // no energy is created
+            assert!(importance.get_red() <= 1.0)
+        }
+    }
+
+    #[test]
+    fn rough_dielectric_resembles_lambertian_surface() {
+        let m = Microfacet::new(0.0, 1.0);
+        let n = vector!(0, 0, -1);
+        let c = m.shade(Color::white(), Color::white(), n, n, n);
+        assert!(float_eq(c.get_red(), c.get_blue()));
+        assert!((0.9..1.1).contains(&c.get_red()))
+    }
+}
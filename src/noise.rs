@@ -0,0 +1,301 @@
+use crate::tuple::{Position, Tuple};
+
+/// Frequency multiplier between successive octaves.
+const LACUNARITY: f64 = 2.0;
+/// Amplitude multiplier between successive octaves.
+const GAIN: f64 = 0.5;
+
+/// Gradient noise function to sample.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Noise {
+    Perlin,
+    Simplex,
+}
+
+impl Noise {
+    /// Noise at `point`, roughly within `-1..=1`.
+    pub fn at(&self, point: Tuple) -> f64 {
+        match self {
+            Noise::Perlin => perlin(point),
+            Noise::Simplex => simplex(point),
+        }
+    }
+
+    /// Fractal Brownian motion; sums `octaves` layers of noise with rising frequency and
+    /// falling amplitude. Stays roughly within `-1..=1`.
+    pub fn fbm(&self, point: Tuple, octaves: usize) -> f64 {
+        self.octaves(point, octaves, |noise| noise)
+    }
+
+    /// Like `fbm`, but sums absolute values, giving sharp creases e.g. for marble or fire.
+    /// Stays within `0..=1`.
+    pub fn turbulence(&self, point: Tuple, octaves: usize) -> f64 {
+        self.octaves(point, octaves, f64::abs)
+    }
+
+    fn octaves(&self, point: Tuple, octaves: usize, layer: impl Fn(f64) -> f64) -> f64 {
+        let mut sum = 0f64;
+        let mut total_amplitude = 0f64;
+        let mut amplitude = 1f64;
+        let mut frequency = 1f64;
+
+        for _ in 0..octaves.max(1) {
+            let scaled = Tuple::new_point(
+                point[Position::X] * frequency,
+                point[Position::Y] * frequency,
+                point[Position::Z] * frequency,
+            );
+            sum += layer(self.at(scaled)) * amplitude;
+            total_amplitude += amplitude;
+            amplitude *= GAIN;
+            frequency *= LACUNARITY;
+        }
+
+        sum / total_amplitude
+    }
+}
+
+/// Ken Perlin's reference permutation of 0..256.
+const PERMUTATION: [u8; 256] = [
+    151, 160, 137, 91, 90, 15, 131, 13, 201, 95, 96, 53, 194, 233, 7, 225, 140, 36, 103, 30, 69,
+    142, 8, 99, 37, 240, 21, 10, 23, 190, 6, 148, 247, 120, 234, 75, 0, 26, 197, 62, 94, 252, 219,
+    203, 117, 35, 11, 32, 57, 177, 33, 88, 237, 149, 56, 87, 174, 20, 125, 136, 171, 168, 68, 175,
+    74, 165, 71, 134, 139, 48, 27, 166, 77, 146, 158, 231, 83, 111, 229, 122, 60, 211, 133, 230,
+    220, 105, 92, 41, 55, 46, 245, 40, 244, 102, 143, 54, 65, 25, 63, 161, 1, 216, 80, 73, 209, 76,
+    132, 187, 208, 89, 18, 169, 200, 196, 135, 130, 116, 188, 159, 86, 164, 100, 109, 198, 173,
+    186, 3, 64, 52, 217, 226, 250, 124, 123, 5, 202, 38, 147, 118, 126, 255, 82, 85, 212, 207, 206,
+    59, 227, 47, 16, 58, 17, 182, 189, 28, 42, 223, 183, 170, 213, 119, 248, 152, 2, 44, 154, 163,
+    70, 221, 153, 101, 155, 167, 43, 172, 9, 129, 22, 39, 253, 19, 98, 108, 110, 79, 113, 224, 232,
+    178, 185, 112, 104, 218, 246, 97, 228, 251, 34, 242, 193, 238, 210, 144, 12, 191, 179, 162,
+    241, 81, 51, 145, 235, 249, 14, 239, 107, 49, 192, 214, 31, 181, 199, 106, 157, 184, 84, 204,
+    176, 115, 121, 50, 45, 127, 4, 150, 254, 138, 236, 205, 93, 222, 114, 67, 29, 24, 72, 243, 141,
+    128, 195, 78, 66, 215, 61, 156, 180,
+];
+
+fn hash(i: i64) -> usize {
+    PERMUTATION[i.rem_euclid(256) as usize] as usize
+}
+
+/// Smoothstep with vanishing first and second derivative at 0 and 1.
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6f64 - 15f64) + 10f64)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+/// Dot product of the offset `(x, y, z)` with one of twelve gradients picked by `hash`.
+fn gradient(hash: usize, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+/// Improved Perlin noise at `point`, roughly within `-1..=1`.
+///
+/// Deterministic and zero at every point with integer coordinates.
+pub fn perlin(point: Tuple) -> f64 {
+    let (x, y, z) = (point[Position::X], point[Position::Y], point[Position::Z]);
+    let (xi, yi, zi) = (x.floor() as i64, y.floor() as i64, z.floor() as i64);
+    let (x, y, z) = (x - x.floor(), y - y.floor(), z - z.floor());
+    let (u, v, w) = (fade(x), fade(y), fade(z));
+
+    // hashes of the eight cube corners around the point
+    let a = hash(xi) as i64 + yi;
+    let aa = hash(a) as i64 + zi;
+    let ab = hash(a + 1) as i64 + zi;
+    let b = hash(xi + 1) as i64 + yi;
+    let ba = hash(b) as i64 + zi;
+    let bb = hash(b + 1) as i64 + zi;
+
+    lerp(
+        w,
+        lerp(
+            v,
+            lerp(
+                u,
+                gradient(hash(aa), x, y, z),
+                gradient(hash(ba), x - 1f64, y, z),
+            ),
+            lerp(
+                u,
+                gradient(hash(ab), x, y - 1f64, z),
+                gradient(hash(bb), x - 1f64, y - 1f64, z),
+            ),
+        ),
+        lerp(
+            v,
+            lerp(
+                u,
+                gradient(hash(aa + 1), x, y, z - 1f64),
+                gradient(hash(ba + 1), x - 1f64, y, z - 1f64),
+            ),
+            lerp(
+                u,
+                gradient(hash(ab + 1), x, y - 1f64, z - 1f64),
+                gradient(hash(bb + 1), x - 1f64, y - 1f64, z - 1f64),
+            ),
+        ),
+    )
+}
+
+/// Gradients towards the edge midpoints of a cube, used by simplex noise.
+const GRADIENTS: [(f64, f64, f64); 12] = [
+    (1.0, 1.0, 0.0),
+    (-1.0, 1.0, 0.0),
+    (1.0, -1.0, 0.0),
+    (-1.0, -1.0, 0.0),
+    (1.0, 0.0, 1.0),
+    (-1.0, 0.0, 1.0),
+    (1.0, 0.0, -1.0),
+    (-1.0, 0.0, -1.0),
+    (0.0, 1.0, 1.0),
+    (0.0, -1.0, 1.0),
+    (0.0, 1.0, -1.0),
+    (0.0, -1.0, -1.0),
+];
+
+/// Simplex noise at `point`, roughly within `-1..=1`.
+///
+/// Cheaper than Perlin noise and without its axis aligned artifacts.
+pub fn simplex(point: Tuple) -> f64 {
+    // factors for skewing into and out of the grid of tetrahedrons
+    const SKEW: f64 = 1.0 / 3.0;
+    const UNSKEW: f64 = 1.0 / 6.0;
+
+    let (x, y, z) = (point[Position::X], point[Position::Y], point[Position::Z]);
+    let s = (x + y + z) * SKEW;
+    let (i, j, k) = ((x + s).floor(), (y + s).floor(), (z + s).floor());
+    let t = (i + j + k) * UNSKEW;
+    let (x0, y0, z0) = (x - (i - t), y - (j - t), z - (k - t));
+
+    // offsets of the second and third corner of the tetrahedron containing the point
+    let ((i1, j1, k1), (i2, j2, k2)) = if x0 >= y0 {
+        if y0 >= z0 {
+            ((1, 0, 0), (1, 1, 0))
+        } else if x0 >= z0 {
+            ((1, 0, 0), (1, 0, 1))
+        } else {
+            ((0, 0, 1), (1, 0, 1))
+        }
+    } else if y0 < z0 {
+        ((0, 0, 1), (0, 1, 1))
+    } else if x0 < z0 {
+        ((0, 1, 0), (0, 1, 1))
+    } else {
+        ((0, 1, 0), (1, 1, 0))
+    };
+
+    let corners = [
+        ((0, 0, 0), (x0, y0, z0)),
+        (
+            (i1, j1, k1),
+            (
+                x0 - i1 as f64 + UNSKEW,
+                y0 - j1 as f64 + UNSKEW,
+                z0 - k1 as f64 + UNSKEW,
+            ),
+        ),
+        (
+            (i2, j2, k2),
+            (
+                x0 - i2 as f64 + 2f64 * UNSKEW,
+                y0 - j2 as f64 + 2f64 * UNSKEW,
+                z0 - k2 as f64 + 2f64 * UNSKEW,
+            ),
+        ),
+        (
+            (1, 1, 1),
+            (
+                x0 - 1f64 + 3f64 * UNSKEW,
+                y0 - 1f64 + 3f64 * UNSKEW,
+                z0 - 1f64 + 3f64 * UNSKEW,
+            ),
+        ),
+    ];
+
+    let (i, j, k) = (i as i64, j as i64, k as i64);
+    let sum: f64 = corners
+        .iter()
+        .map(|&((di, dj, dk), (x, y, z))| {
+            let falloff = 0.6 - x * x - y * y - z * z;
+            if falloff < 0f64 {
+                return 0f64;
+            }
+            let index = hash(i + di + hash(j + dj + hash(k + dk) as i64) as i64) % 12;
+            let (gx, gy, gz) = GRADIENTS[index];
+            falloff.powi(4) * (gx * x + gy * y + gz * z)
+        })
+        .sum();
+
+    // scales the result into -1..=1
+    32f64 * sum
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{point, tuple::Tuple};
+
+    use super::{perlin, simplex, Noise};
+
+    #[test]
+    fn perlin_noise_vanishes_at_lattice_points() {
+        for point in [point!(0, 0, 0), point!(1, 2, 3), point!(-4, 7, -2)] {
+            assert_eq!(perlin(point), 0.0)
+        }
+    }
+
+    #[test]
+    fn perlin_noise_is_deterministic_and_bounded() {
+        let mut varies = false;
+        for i in 0..1000 {
+            let p = point!(i as f64 * 0.137, i as f64 * 0.071 - 3.0, i as f64 * 0.293);
+            let n = perlin(p);
+            assert_eq!(n, perlin(p));
+            assert!((-1.0..=1.0).contains(&n));
+            varies |= n.abs() > 0.1;
+        }
+        assert!(varies)
+    }
+
+    #[test]
+    fn simplex_noise_is_deterministic_and_bounded() {
+        let mut varies = false;
+        for i in 0..1000 {
+            let p = point!(i as f64 * 0.137, i as f64 * 0.071 - 3.0, i as f64 * 0.293);
+            let n = simplex(p);
+            assert_eq!(n, simplex(p));
+            assert!((-1.0..=1.0).contains(&n));
+            varies |= n.abs() > 0.1;
+        }
+        assert!(varies)
+    }
+
+    #[test]
+    fn single_octave_equals_plain_noise() {
+        let p = point!(0.3, 1.7, -2.2);
+        for noise in [Noise::Perlin, Noise::Simplex] {
+            assert_eq!(noise.fbm(p, 1), noise.at(p));
+            assert_eq!(noise.turbulence(p, 1), noise.at(p).abs())
+        }
+    }
+
+    #[test]
+    fn fractal_noise_stays_in_range() {
+        for i in 0..200 {
+            let p = point!(i as f64 * 0.31, i as f64 * 0.17, -(i as f64) * 0.07);
+            for noise in [Noise::Perlin, Noise::Simplex] {
+                assert!((-1.0..=1.0).contains(&noise.fbm(p, 5)));
+                assert!((0.0..=1.0).contains(&noise.turbulence(p, 5)))
+            }
+        }
+    }
+}
@@ -0,0 +1,363 @@
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{
+    matrix::Matrix,
+    noise::Noise,
+    pattern::texture_map::{UvMapping, UvPattern},
+    ray::intersection::Intersection,
+    tuple::{Position, Tuple},
+    utils::EPSILON,
+    vector,
+};
+
+/// Distance between samples when differentiating height fields and UV mappings.
+const GRADIENT_STEP: f64 = 0.0001;
+
+/// Converts a gradient from the space of a perturbation with the given `inverse`
+/// transformation into world space. Gradients transform like normals.
+fn gradient_to_world(inverse: Matrix<4, 4>, gradient: Tuple, intersection: &Intersection) -> Tuple {
+    let gradient = inverse.transpose() * gradient;
+    intersection.gradient_to_world(vector!(
+        gradient[Position::X],
+        gradient[Position::Y],
+        gradient[Position::Z]
+    ))
+}
+
+/// Adds surface detail by altering the normal of a body before it gets shaded.
+pub trait NormalPerturbation: Debug + Send + Sync {
+    /// Perturbs the world space `normal` at `point`, given in object space, of the body hit by
+    /// `intersection`. Returns a normalized world space vector.
+    fn perturb(&self, point: Tuple, normal: Tuple, intersection: &Intersection) -> Tuple;
+}
+
+/// Scalar height above the surface, used by `BumpMap`.
+pub trait HeightField: Debug + Send + Sync {
+    fn height_at(&self, point: Tuple) -> f64;
+}
+
+impl HeightField for Noise {
+    fn height_at(&self, point: Tuple) -> f64 {
+        self.at(point)
+    }
+}
+
+/// Tilts the normal along the gradient of a height field, making the surface look bumpy.
+#[derive(Clone, Debug)]
+pub struct BumpMap {
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    height: Arc<dyn HeightField>,
+    scale: f64,
+}
+
+impl BumpMap {
+    pub fn new<H: HeightField + 'static>(height: H) -> Self {
+        Self {
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            height: Arc::new(height),
+            scale: 1.0,
+        }
+    }
+
+    /// Transforms the height field relative to the body, like a pattern.
+    pub fn transform(&self, by: Matrix<4, 4>) -> Self {
+        let transformation = self.transformation * by;
+        Self {
+            transformation,
+            inverse: transformation.inverse(),
+            ..self.clone()
+        }
+    }
+
+    /// Strength of the bumps; `0` leaves the normal untouched.
+    pub fn set_scale(&self, scale: f64) -> Self {
+        Self {
+            scale,
+            ..self.clone()
+        }
+    }
+
+    pub fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    pub fn get_scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Gradient of the height field in the space of the bump map (central differences).
+    fn gradient(&self, point: Tuple) -> Tuple {
+        let difference = |offset: Tuple| {
+            (self.height.height_at(point + offset) - self.height.height_at(point - offset))
+                / (2f64 * GRADIENT_STEP)
+        };
+
+        vector!(
+            difference(vector!(GRADIENT_STEP, 0, 0)),
+            difference(vector!(0, GRADIENT_STEP, 0)),
+            difference(vector!(0, 0, GRADIENT_STEP))
+        )
+    }
+}
+
+impl NormalPerturbation for BumpMap {
+    fn perturb(&self, point: Tuple, normal: Tuple, intersection: &Intersection) -> Tuple {
+        let gradient = gradient_to_world(
+            self.inverse,
+            self.gradient(self.inverse * point),
+            intersection,
+        );
+
+        // only the part of the gradient along the surface tilts the normal
+        let tangential = gradient - normal * gradient.dot(normal);
+        (normal - tangential * self.scale).normalize()
+    }
+}
+
+/// Replaces the normal by one read from a tangent space normal map, e.g. an `ImageTexture`
+/// with the usual encoding of `(x, y, z)` as `((x + 1) / 2, (y + 1) / 2, (z + 1) / 2)`.
+///
+/// The tangent points where `u` of the mapping grows along the surface, so the normal map
+/// follows the mapping and every transformation of the body. Where that direction is
+/// undefined, e.g. at the poles of a spherical mapping, the tangent points around the y axis
+/// instead.
+#[derive(Clone, Debug)]
+pub struct NormalMap {
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    texture: Arc<dyn UvPattern>,
+    mapping: UvMapping,
+}
+
+impl NormalMap {
+    pub fn new<P: UvPattern + 'static>(texture: P, mapping: UvMapping) -> Self {
+        Self {
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            texture: Arc::new(texture),
+            mapping,
+        }
+    }
+
+    /// Transforms the normal map relative to the body, like a pattern.
+    pub fn transform(&self, by: Matrix<4, 4>) -> Self {
+        let transformation = self.transformation * by;
+        Self {
+            transformation,
+            inverse: transformation.inverse(),
+            ..self.clone()
+        }
+    }
+
+    pub fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    pub fn get_mapping(&self) -> UvMapping {
+        self.mapping
+    }
+
+    /// Gradient of `u` in the space of the normal map (central differences), ignoring the
+    /// jump where `u` wraps around.
+    fn u_gradient(&self, point: Tuple) -> Tuple {
+        let difference = |offset: Tuple| {
+            let (u_ahead, _) = self.mapping.map(point + offset);
+            let (u_behind, _) = self.mapping.map(point - offset);
+            let difference = u_ahead - u_behind;
+            (difference - difference.round()) / (2f64 * GRADIENT_STEP)
+        };
+
+        vector!(
+            difference(vector!(GRADIENT_STEP, 0, 0)),
+            difference(vector!(0, GRADIENT_STEP, 0)),
+            difference(vector!(0, 0, GRADIENT_STEP))
+        )
+    }
+
+    /// Tangent and bitangent completing the world space `normal` to an orthonormal frame.
+    fn tangent_frame(
+        &self,
+        point: Tuple,
+        normal: Tuple,
+        intersection: &Intersection,
+    ) -> (Tuple, Tuple) {
+        let gradient = gradient_to_world(self.inverse, self.u_gradient(point), intersection);
+        let tangent = gradient - normal * gradient.dot(normal);
+        let tangent = if tangent.magnitude() > EPSILON {
+            tangent.normalize()
+        } else {
+            let up = vector!(0, 1, 0);
+            let tangent = if normal.dot(up).abs() > 0.999 {
+                vector!(1, 0, 0)
+            } else {
+                normal.cross(up)
+            };
+            (tangent - normal * tangent.dot(normal)).normalize()
+        };
+        (tangent, tangent.cross(normal))
+    }
+}
+
+impl NormalPerturbation for NormalMap {
+    fn perturb(&self, point: Tuple, normal: Tuple, intersection: &Intersection) -> Tuple {
+        let point = self.inverse * point;
+        let (u, v) = self.mapping.map(point);
+        let color = self.texture.uv_color_at(u, v);
+        let (tangent, bitangent) = self.tangent_frame(point, normal, intersection);
+
+        (tangent * (2f64 * color.get_red() - 1f64)
+            + bitangent * (2f64 * color.get_green() - 1f64)
+            + normal * (2f64 * color.get_blue() - 1f64))
+            .normalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::{
+        body::{plane::Plane, sphere::Sphere, Body},
+        color::Color,
+        material::Material,
+        matrix::{transformation::Axis, Matrix},
+        pattern::texture_map::{UvChecker, UvMapping},
+        point,
+        ray::{intersection::Intersection, Ray},
+        tuple::{Position, Tuple},
+        vector,
+    };
+
+    use super::{BumpMap, HeightField, NormalMap, NormalPerturbation};
+
+    /// Height rising linearly along x.
+    #[derive(Debug)]
+    struct Slope;
+
+    impl HeightField for Slope {
+        fn height_at(&self, point: Tuple) -> f64 {
+            point[Position::X]
+        }
+    }
+
+    /// Same height everywhere.
+    #[derive(Debug)]
+    struct Flat;
+
+    impl HeightField for Flat {
+        fn height_at(&self, _point: Tuple) -> f64 {
+            0.5
+        }
+    }
+
+    fn uniform(color: Color) -> UvChecker {
+        UvChecker::new(1.0, 1.0, color, color)
+    }
+
+    #[test]
+    fn flat_height_field_keeps_normal() {
+        let p = Plane::new();
+        let i = Intersection::new(1.0, &p);
+        let bump = BumpMap::new(Flat);
+        let n = bump.perturb(point!(0.3, 0, 0.2), vector!(0, 1, 0), &i);
+        assert_eq!(n, vector!(0, 1, 0))
+    }
+
+    #[test]
+    fn slope_tilts_normal_against_gradient() {
+        let p = Plane::new();
+        let i = Intersection::new(1.0, &p);
+        let bump = BumpMap::new(Slope);
+        let n = bump.perturb(point!(0.3, 0, 0.2), vector!(0, 1, 0), &i);
+        assert_eq!(n, vector!(-2f64.sqrt() / 2.0, 2f64.sqrt() / 2.0, 0));
+
+        let n = bump
+            .set_scale(0.0)
+            .perturb(point!(0.3, 0, 0.2), vector!(0, 1, 0), &i);
+        assert_eq!(n, vector!(0, 1, 0))
+    }
+
+    #[test]
+    fn bump_map_follows_transformations() {
+        // stretching the height field halves its gradient
+        let p = Plane::new();
+        let i = Intersection::new(1.0, &p);
+        let bump = BumpMap::new(Slope).transform(Matrix::scaling_matrix(2.0, 1.0, 1.0));
+        let n = bump.perturb(point!(0.3, 0, 0.2), vector!(0, 1, 0), &i);
+        assert_eq!(n, vector!(-0.5, 1, 0).normalize());
+
+        // scaling the body along x does the same in world space
+        let p = Plane::new().transform(Matrix::scaling_matrix(2.0, 1.0, 1.0));
+        let i = Intersection::new(1.0, &p);
+        let n = BumpMap::new(Slope).perturb(point!(0.3, 0, 0.2), vector!(0, 1, 0), &i);
+        assert_eq!(n, vector!(-0.5, 1, 0).normalize())
+    }
+
+    #[test]
+    fn neutral_normal_map_keeps_normal() {
+        let p = Plane::new();
+        let i = Intersection::new(1.0, &p);
+        let map = NormalMap::new(uniform(Color::new(0.5, 0.5, 1.0)), UvMapping::Planar);
+        let n = map.perturb(point!(0.3, 0, 0.2), vector!(0, 1, 0), &i);
+        assert_eq!(n, vector!(0, 1, 0))
+    }
+
+    #[test]
+    fn normal_map_uses_tangent_frame() {
+        let p = Plane::new();
+        let i = Intersection::new(1.0, &p);
+        let map = NormalMap::new(uniform(Color::new(1.0, 0.5, 0.5)), UvMapping::Planar);
+        let n = map.perturb(point!(0.3, 0, 0.2), vector!(0, 1, 0), &i);
+        assert_eq!(n, vector!(1, 0, 0));
+
+        // around a sphere the tangent follows u of the spherical mapping
+        let map = NormalMap::new(uniform(Color::new(1.0, 0.5, 0.5)), UvMapping::Spherical);
+        let n = map.perturb(point!(0, 0, -1), vector!(0, 0, -1), &i);
+        assert_eq!(n, vector!(1, 0, 0));
+        let map = NormalMap::new(uniform(Color::new(0.5, 1.0, 0.5)), UvMapping::Spherical);
+        let n = map.perturb(point!(0, 0, -1), vector!(0, 0, -1), &i);
+        assert_eq!(n, vector!(0, 1, 0))
+    }
+
+    #[test]
+    fn normal_map_rotates_with_body() {
+        // turning a plane about its normal turns the planar mapping, and the tangent with it
+        let p = Plane::new().transform(Matrix::rotation_matrix(Axis::Y, PI / 2.0));
+        let i = Intersection::new(1.0, &p);
+        let map = NormalMap::new(uniform(Color::new(1.0, 0.5, 0.5)), UvMapping::Planar);
+        let n = map.perturb(point!(0.3, 0, 0.2), vector!(0, 1, 0), &i);
+        assert_eq!(n, vector!(0, 0, -1));
+        let map = NormalMap::new(uniform(Color::new(0.5, 0.5, 1.0)), UvMapping::Planar);
+        let n = map.perturb(point!(0.3, 0, 0.2), vector!(0, 1, 0), &i);
+        assert_eq!(n, vector!(0, 1, 0));
+
+        // same for a sphere turned about z inside a scaled group
+        let s = Sphere::new().transform(Matrix::rotation_matrix(Axis::Z, PI / 2.0));
+        let i =
+            Intersection::new(1.0, &s).with_parent(Matrix::scaling_matrix(2.0, 2.0, 2.0).inverse());
+        let normal = i.normal_to_world(s.normal_at(i.world_to_object(point!(0, 0, -2))));
+        assert_eq!(normal, vector!(0, 0, -1));
+        let map = NormalMap::new(uniform(Color::new(1.0, 0.5, 0.5)), UvMapping::Spherical);
+        let n = map.perturb(point!(0, 0, -1), normal, &i);
+        assert_eq!(n, vector!(0, 1, 0));
+        let map = NormalMap::new(uniform(Color::new(0.5, 1.0, 0.5)), UvMapping::Spherical);
+        let n = map.perturb(point!(0, 0, -1), normal, &i);
+        assert_eq!(n, vector!(-1, 0, 0))
+    }
+
+    #[test]
+    fn perturbed_normal_is_used_for_shading() {
+        let p = Plane::new()
+            .set_material(Material::default().set_normal_perturbation(BumpMap::new(Slope)));
+        let r = Ray::new(point!(0, 1, 0), vector!(0, -1, 0));
+        let i = Intersection::new(1.0, &p);
+        let comps = i.prepare_computations(&r, &[i]);
+        assert_eq!(
+            comps.normalv,
+            vector!(-2f64.sqrt() / 2.0, 2f64.sqrt() / 2.0, 0)
+        );
+        assert_eq!(comps.reflectv, vector!(-1, 0, 0))
+    }
+}
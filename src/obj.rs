@@ -0,0 +1,552 @@
+use std::{fs, path::Path, sync::Arc};
+
+use crate::{
+    body::{group::Group, smooth_triangle::SmoothTriangle, triangle::Triangle, Body},
+    tuple::Tuple,
+    world::World,
+};
+
+/// Name of the group collecting all faces declared before the first `g` or `o` statement.
+pub const DEFAULT_GROUP: &str = "default";
+
+/// Triangles of one `g`/`o` group of a Wavefront OBJ file.
+///
+/// Faces with normals for every vertex become `SmoothTriangle`s, all others `Triangle`s.
+#[derive(Clone, Debug)]
+pub struct ObjGroup {
+    name: String,
+    triangles: Vec<Triangle>,
+    smooth_triangles: Vec<SmoothTriangle>,
+}
+
+impl ObjGroup {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            triangles: vec![],
+            smooth_triangles: vec![],
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_triangles(&self) -> &Vec<Triangle> {
+        &self.triangles
+    }
+
+    pub fn get_smooth_triangles(&self) -> &Vec<SmoothTriangle> {
+        &self.smooth_triangles
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty() && self.smooth_triangles.is_empty()
+    }
+
+    pub fn to_group(&self) -> Group {
+        let triangles = self
+            .triangles
+            .iter()
+            .map(|triangle| Arc::new(triangle.clone()) as Arc<dyn Body>);
+        let smooth_triangles = self
+            .smooth_triangles
+            .iter()
+            .map(|triangle| Arc::new(triangle.clone()) as Arc<dyn Body>);
+        Group::from_children(triangles.chain(smooth_triangles).collect())
+    }
+}
+
+/// Parser for Wavefront OBJ files.
+///
+/// Supports vertices (`v`), vertex normals (`vn`), texture vertices (`vt`), polygonal faces
+/// (`f`, triangulated as a fan) and groups (`g`, `o`). Texture vertices are recorded, but the
+/// texture indices of faces are skipped, as triangles carry no texture coordinates. Every other
+/// statement, as well as malformed ones, is skipped and counted in `get_ignored_lines`.
+#[derive(Clone, Debug)]
+pub struct ObjParser {
+    vertices: Vec<Tuple>,
+    normals: Vec<Tuple>,
+    texture_vertices: Vec<(f64, f64)>,
+    /// Groups in order of their first appearance, starting with the default group.
+    groups: Vec<ObjGroup>,
+    /// Index of the group receiving the following faces.
+    current_group: usize,
+    ignored_lines: usize,
+}
+
+/// Vertex of a face statement; zero based indices into the parser's lists.
+struct FaceVertex {
+    vertex: usize,
+    normal: Option<usize>,
+}
+
+impl ObjParser {
+    pub fn parse(input: &str) -> Self {
+        let mut parser = Self {
+            vertices: vec![],
+            normals: vec![],
+            texture_vertices: vec![],
+            groups: vec![ObjGroup::new(DEFAULT_GROUP)],
+            current_group: 0,
+            ignored_lines: 0,
+        };
+
+        for line in input.lines() {
+            if !parser.parse_line(line) {
+                parser.ignored_lines += 1;
+            }
+        }
+
+        parser
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        let parser = Self::parse(&fs::read_to_string(&path)?);
+        if parser.ignored_lines > 0 {
+            println!(
+                "Ignored {} unsupported lines in {} ...",
+                parser.ignored_lines,
+                path.as_ref().display()
+            );
+        }
+        Ok(parser)
+    }
+
+    /// Returns the vertex with the given one based index, as used in OBJ files.
+    pub fn get_vertex(&self, index: usize) -> Option<Tuple> {
+        index
+            .checked_sub(1)
+            .and_then(|i| self.vertices.get(i).copied())
+    }
+
+    /// Returns the normal with the given one based index, as used in OBJ files.
+    pub fn get_normal(&self, index: usize) -> Option<Tuple> {
+        index
+            .checked_sub(1)
+            .and_then(|i| self.normals.get(i).copied())
+    }
+
+    /// Returns the texture vertex with the given one based index, as used in OBJ files.
+    pub fn get_texture_vertex(&self, index: usize) -> Option<(f64, f64)> {
+        index
+            .checked_sub(1)
+            .and_then(|i| self.texture_vertices.get(i).copied())
+    }
+
+    pub fn get_groups(&self) -> &Vec<ObjGroup> {
+        &self.groups
+    }
+
+    pub fn get_group(&self, name: &str) -> Option<&ObjGroup> {
+        self.groups.iter().find(|group| group.name == name)
+    }
+
+    pub fn get_default_group(&self) -> &ObjGroup {
+        &self.groups[0]
+    }
+
+    pub fn get_ignored_lines(&self) -> usize {
+        self.ignored_lines
+    }
+
+    /// Converts the parsed file into a single group with one child group per non-empty
+    /// OBJ group.
+    pub fn to_group(&self) -> Group {
+        Group::from_children(
+            self.groups
+                .iter()
+                .filter(|group| !group.is_empty())
+                .map(|obj_group| Arc::new(obj_group.to_group()) as Arc<dyn Body>)
+                .collect(),
+        )
+    }
+
+    /// Adds the triangles of all groups to `world`.
+    pub fn add_to_world(&self, world: &mut World) {
+        for group in &self.groups {
+            for triangle in &group.triangles {
+                world.add_object(triangle.clone());
+            }
+            for triangle in &group.smooth_triangles {
+                world.add_object(triangle.clone());
+            }
+        }
+    }
+
+    /// Parses a single line, returns `false` if it has to be ignored.
+    fn parse_line(&mut self, line: &str) -> bool {
+        // comments and blank lines are valid, but carry no information
+        let line = line.split('#').next().unwrap_or_default();
+        let mut tokens = line.split_whitespace();
+        let keyword = match tokens.next() {
+            Some(keyword) => keyword,
+            None => return true,
+        };
+        let arguments: Vec<&str> = tokens.collect();
+
+        match keyword {
+            "v" => match Self::parse_floats::<3>(&arguments) {
+                Some([x, y, z]) => {
+                    self.vertices.push(Tuple::new_point(x, y, z));
+                    true
+                }
+                None => false,
+            },
+            "vn" => match Self::parse_floats::<3>(&arguments) {
+                Some([x, y, z]) => {
+                    self.normals.push(Tuple::new_vec(x, y, z));
+                    true
+                }
+                None => false,
+            },
+            "vt" => match Self::parse_floats::<2>(&arguments) {
+                Some([u, v]) => {
+                    self.texture_vertices.push((u, v));
+                    true
+                }
+                None => false,
+            },
+            "f" => self.parse_face(&arguments),
+            "g" | "o" => {
+                let name = arguments.join(" ");
+                // a reopened group receives the following faces in its original place
+                self.current_group = match self.groups.iter().position(|group| group.name == name) {
+                    Some(index) => index,
+                    None => {
+                        self.groups.push(ObjGroup::new(&name));
+                        self.groups.len() - 1
+                    }
+                };
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Parses the leading `N` arguments as floats; additional ones (e.g. `w`) are ignored.
+    fn parse_floats<const N: usize>(arguments: &[&str]) -> Option<[f64; N]> {
+        if arguments.len() < N {
+            return None;
+        }
+
+        let mut result = [0f64; N];
+        for (i, argument) in arguments.iter().take(N).enumerate() {
+            result[i] = argument.parse().ok()?;
+        }
+        Some(result)
+    }
+
+    /// Resolves an OBJ index, which is one based or, if negative, relative to the end.
+    fn resolve_index(index: &str, count: usize) -> Option<usize> {
+        let index: i64 = index.parse().ok()?;
+        let resolved = if index < 0 {
+            count as i64 + index
+        } else {
+            index - 1
+        };
+
+        if (0..count as i64).contains(&resolved) {
+            Some(resolved as usize)
+        } else {
+            None
+        }
+    }
+
+    fn parse_face_vertex(&self, argument: &str) -> Option<FaceVertex> {
+        // `v`, `v/vt`, `v//vn` or `v/vt/vn`; the texture index is not used
+        let mut parts = argument.split('/');
+        let vertex = Self::resolve_index(parts.next()?, self.vertices.len())?;
+        parts.next();
+
+        let normal = match parts.next() {
+            Some(normal) if !normal.is_empty() => {
+                Some(Self::resolve_index(normal, self.normals.len())?)
+            }
+            _ => None,
+        };
+
+        Some(FaceVertex { vertex, normal })
+    }
+
+    fn parse_face(&mut self, arguments: &[&str]) -> bool {
+        if arguments.len() < 3 {
+            return false;
+        }
+
+        let mut face = vec![];
+        for argument in arguments {
+            match self.parse_face_vertex(argument) {
+                Some(vertex) => face.push(vertex),
+                None => return false,
+            }
+        }
+
+        let smooth = face.iter().all(|vertex| vertex.normal.is_some());
+        let group = &mut self.groups[self.current_group];
+
+        // fan triangulation around the first vertex
+        for i in 1..face.len() - 1 {
+            let corners = [&face[0], &face[i], &face[i + 1]];
+            let [p1, p2, p3] = corners.map(|corner| self.vertices[corner.vertex]);
+
+            if smooth {
+                let [n1, n2, n3] = corners.map(|corner| self.normals[corner.normal.unwrap()]);
+                group
+                    .smooth_triangles
+                    .push(SmoothTriangle::from_points(p1, p2, p3, n1, n2, n3));
+            } else {
+                group.triangles.push(Triangle::from_points(p1, p2, p3));
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{body::Body, point, ray::Ray, tuple::Tuple, vector, world::World};
+
+    use super::{ObjParser, DEFAULT_GROUP};
+
+    #[test]
+    fn ignoring_unrecognized_lines() {
+        let gibberish = "There was a young lady named Bright\n\
+            who traveled much faster than light.\n\
+            She set out one day\n\
+            in a relative way,\n\
+            and came back the previous night.";
+        let parser = ObjParser::parse(gibberish);
+        assert_eq!(parser.get_ignored_lines(), 5);
+        assert!(parser.get_default_group().is_empty())
+    }
+
+    #[test]
+    fn comments_and_blank_lines_are_not_ignored() {
+        let parser = ObjParser::parse("# a comment\n\nv 1 2 3 # trailing\n");
+        assert_eq!(parser.get_ignored_lines(), 0);
+        assert_eq!(parser.get_vertex(1), Some(point!(1, 2, 3)))
+    }
+
+    #[test]
+    fn vertex_records() {
+        let file = "v -1 1 0\n\
+            v -1.0000 0.5000 0.0000\n\
+            v 1 0 0\n\
+            v 1 1 0";
+        let parser = ObjParser::parse(file);
+        assert_eq!(parser.get_vertex(1), Some(point!(-1, 1, 0)));
+        assert_eq!(parser.get_vertex(2), Some(point!(-1, 0.5, 0)));
+        assert_eq!(parser.get_vertex(3), Some(point!(1, 0, 0)));
+        assert_eq!(parser.get_vertex(4), Some(point!(1, 1, 0)));
+        assert_eq!(parser.get_vertex(0), None);
+        assert_eq!(parser.get_vertex(5), None)
+    }
+
+    #[test]
+    fn parsing_triangle_faces() {
+        let file = "v -1 1 0\n\
+            v -1 0 0\n\
+            v 1 0 0\n\
+            v 1 1 0\n\
+            \n\
+            f 1 2 3\n\
+            f 1 3 4";
+        let parser = ObjParser::parse(file);
+        let triangles = parser.get_default_group().get_triangles();
+        assert_eq!(triangles.len(), 2);
+        assert_eq!(
+            triangles[0].get_points(),
+            (point!(-1, 1, 0), point!(-1, 0, 0), point!(1, 0, 0))
+        );
+        assert_eq!(
+            triangles[1].get_points(),
+            (point!(-1, 1, 0), point!(1, 0, 0), point!(1, 1, 0))
+        )
+    }
+
+    #[test]
+    fn triangulating_polygons() {
+        let file = "v -1 1 0\n\
+            v -1 0 0\n\
+            v 1 0 0\n\
+            v 1 1 0\n\
+            v 0 2 0\n\
+            \n\
+            f 1 2 3 4 5";
+        let parser = ObjParser::parse(file);
+        let triangles = parser.get_default_group().get_triangles();
+        assert_eq!(triangles.len(), 3);
+        assert_eq!(
+            triangles[0].get_points(),
+            (point!(-1, 1, 0), point!(-1, 0, 0), point!(1, 0, 0))
+        );
+        assert_eq!(
+            triangles[1].get_points(),
+            (point!(-1, 1, 0), point!(1, 0, 0), point!(1, 1, 0))
+        );
+        assert_eq!(
+            triangles[2].get_points(),
+            (point!(-1, 1, 0), point!(1, 1, 0), point!(0, 2, 0))
+        )
+    }
+
+    #[test]
+    fn triangles_in_groups() {
+        let file = "v -1 1 0\n\
+            v -1 0 0\n\
+            v 1 0 0\n\
+            v 1 1 0\n\
+            \n\
+            g FirstGroup\n\
+            f 1 2 3\n\
+            o SecondGroup\n\
+            f 1 3 4";
+        let parser = ObjParser::parse(file);
+        assert!(parser.get_group(DEFAULT_GROUP).unwrap().is_empty());
+        let first = parser.get_group("FirstGroup").unwrap();
+        let second = parser.get_group("SecondGroup").unwrap();
+        assert_eq!(
+            first.get_triangles()[0].get_points(),
+            (point!(-1, 1, 0), point!(-1, 0, 0), point!(1, 0, 0))
+        );
+        assert_eq!(
+            second.get_triangles()[0].get_points(),
+            (point!(-1, 1, 0), point!(1, 0, 0), point!(1, 1, 0))
+        )
+    }
+
+    #[test]
+    fn reopening_groups() {
+        let file = "v -1 1 0\n\
+            v -1 0 0\n\
+            v 1 0 0\n\
+            v 1 1 0\n\
+            \n\
+            f 1 2 3\n\
+            g FirstGroup\n\
+            f 1 2 3\n\
+            g default\n\
+            f 1 3 4\n\
+            g SecondGroup\n\
+            f 1 3 4\n\
+            g FirstGroup\n\
+            f 1 3 4";
+        let parser = ObjParser::parse(file);
+        let names: Vec<&str> = parser.get_groups().iter().map(|g| g.get_name()).collect();
+        assert_eq!(names, [DEFAULT_GROUP, "FirstGroup", "SecondGroup"]);
+        assert_eq!(parser.get_default_group().get_name(), DEFAULT_GROUP);
+        assert_eq!(parser.get_default_group().get_triangles().len(), 2);
+
+        let first = parser.get_group("FirstGroup").unwrap();
+        let second = parser.get_group("SecondGroup").unwrap();
+        assert_eq!(first.get_triangles().len(), 2);
+        assert_eq!(second.get_triangles().len(), 1)
+    }
+
+    #[test]
+    fn vertex_normal_and_texture_records() {
+        let file = "vn 0 0 1\n\
+            vn 0.707 0 -0.707\n\
+            vn 1 2 3\n\
+            vt 0.5 0.25";
+        let parser = ObjParser::parse(file);
+        assert_eq!(parser.get_normal(1), Some(vector!(0, 0, 1)));
+        assert_eq!(parser.get_normal(2), Some(vector!(0.707, 0, -0.707)));
+        assert_eq!(parser.get_normal(3), Some(vector!(1, 2, 3)));
+        assert_eq!(parser.get_texture_vertex(1), Some((0.5, 0.25)))
+    }
+
+    #[test]
+    fn faces_with_normals() {
+        let file = "v 0 1 0\n\
+            v -1 0 0\n\
+            v 1 0 0\n\
+            \n\
+            vn -1 0 0\n\
+            vn 1 0 0\n\
+            vn 0 1 0\n\
+            vt 0 0\n\
+            \n\
+            f 1//3 2//1 3//2\n\
+            f 1/1/3 2/1/1 3/1/2";
+        let parser = ObjParser::parse(file);
+        let triangles = parser.get_default_group().get_smooth_triangles();
+        assert_eq!(parser.get_ignored_lines(), 0);
+        assert_eq!(triangles.len(), 2);
+        for triangle in triangles {
+            assert_eq!(
+                triangle.get_points(),
+                (point!(0, 1, 0), point!(-1, 0, 0), point!(1, 0, 0))
+            );
+            assert_eq!(
+                triangle.get_normals(),
+                (vector!(0, 1, 0), vector!(-1, 0, 0), vector!(1, 0, 0))
+            )
+        }
+    }
+
+    #[test]
+    fn negative_indices_are_relative_to_end() {
+        let file = "v -1 1 0\n\
+            v -1 0 0\n\
+            v 1 0 0\n\
+            f -3 -2 -1";
+        let parser = ObjParser::parse(file);
+        assert_eq!(
+            parser.get_default_group().get_triangles()[0].get_points(),
+            (point!(-1, 1, 0), point!(-1, 0, 0), point!(1, 0, 0))
+        )
+    }
+
+    #[test]
+    fn malformed_statements_are_counted() {
+        let file = "v 1 2\n\
+            v 1 0 0\n\
+            v 0 1 0\n\
+            v 0 0 1\n\
+            f 1 2 7\n\
+            f 1 2\n\
+            usemtl shiny\n\
+            f 1 2 3";
+        let parser = ObjParser::parse(file);
+        assert_eq!(parser.get_ignored_lines(), 4);
+        assert_eq!(parser.get_default_group().get_triangles().len(), 1)
+    }
+
+    #[test]
+    fn adding_parsed_triangles_to_world() {
+        let file = "v 0 1 0\n\
+            v -1 0 0\n\
+            v 1 0 0\n\
+            v 1 1 0\n\
+            vn 0 0 -1\n\
+            f 1 2 3\n\
+            g Smooth\n\
+            f 1//1 3//1 4//1";
+        let parser = ObjParser::parse(file);
+        let mut world = World::new();
+        parser.add_to_world(&mut world);
+        assert_eq!(world.get_objects().len(), 2)
+    }
+
+    #[test]
+    fn converting_obj_file_to_group() {
+        let file = "v -1 1 0\n\
+            v -1 0 0\n\
+            v 1 0 0\n\
+            v 1 1 0\n\
+            \n\
+            g FirstGroup\n\
+            f 1 2 3\n\
+            g SecondGroup\n\
+            f 1 3 4";
+        let parser = ObjParser::parse(file);
+        let group = parser.to_group();
+        assert_eq!(group.get_children().len(), 2);
+
+        let r = Ray::new(point!(0.5, 0.75, -5), vector!(0, 0, 1));
+        assert_eq!(group.intersect(&r).len(), 1)
+    }
+}
@@ -0,0 +1,277 @@
+use crate::{
+    camera::Camera,
+    color::Color,
+    ray::{intersection::Intersection, Ray},
+    sampling::{self, Rng},
+    world::World,
+};
+
+/// Number of paths traced per pixel by default.
+pub const DEFAULT_SAMPLES: usize = 16;
+/// Number of bounces before paths may be terminated by Russian roulette by default.
+pub const DEFAULT_MIN_DEPTH: usize = 3;
+
+/// Monte Carlo path tracer, an alternative to the Whitted-style `World::color_at` that also
+/// computes indirect light, e.g. colors bleeding from one surface onto another.
+///
+/// At every bounce the light arriving directly from every light source is gathered (next
+/// event estimation). The path then continues into one direction: reflected, refracted or
+/// scattered, chosen with a probability proportional to the respective share of the material.
+/// Scattered directions are sampled by importance of the microfacet model of the material, or
+/// cosine weighted otherwise. Instead of a fixed depth, paths are terminated by Russian
+/// roulette, which keeps the estimate unbiased.
+///
+/// Indirect light replaces the ambient term of materials, which is ignored.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PathTracer {
+    samples: usize,
+    min_depth: usize,
+}
+
+impl Default for PathTracer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PathTracer {
+    pub fn new() -> Self {
+        Self {
+            samples: DEFAULT_SAMPLES,
+            min_depth: DEFAULT_MIN_DEPTH,
+        }
+    }
+
+    /// Number of paths averaged per pixel; more samples mean less noise.
+    pub fn set_samples(&self, samples: usize) -> Self {
+        if samples == 0 {
+            panic!("Samples out of range (>=1)");
+        }
+
+        Self { samples, ..*self }
+    }
+
+    /// Number of bounces every path takes before Russian roulette may terminate it.
+    pub fn set_min_depth(&self, min_depth: usize) -> Self {
+        Self { min_depth, ..*self }
+    }
+
+    pub fn get_samples(&self) -> usize {
+        self.samples
+    }
+
+    pub fn get_min_depth(&self) -> usize {
+        self.min_depth
+    }
+
+    /// Averages paths through random points within pixel `(x, y)` of the camera.
+    pub fn pixel_color(&self, world: &World, camera: &Camera, x: usize, y: usize) -> Color {
+        let mut sum = Color::black();
+        for sample in 0..self.samples {
+            let seed = ((y * camera.get_hsize() + x) * self.samples + sample) as u64;
+            let mut rng = Rng::new(seed);
+            let ray = camera.ray_through(x as f64 + rng.next_f64(), y as f64 + rng.next_f64());
+            sum += self.trace(world, ray, &mut rng);
+        }
+        sum * (1f64 / self.samples as f64)
+    }
+
+    /// Color seen along a single path starting with `ray`; `seed` determines the random
+    /// choices along the path.
+    pub fn color_at(&self, world: &World, ray: Ray, seed: u64) -> Color {
+        self.trace(world, ray, &mut Rng::new(seed))
+    }
+
+    fn trace(&self, world: &World, ray: Ray, rng: &mut Rng) -> Color {
+        let mut ray = ray;
+        let mut radiance = Color::black();
+        // share of the light at the current vertex reaching the eye
+        let mut throughput = Color::white();
+
+        for depth in 0.. {
+            let mut intersections = world.intersect(&ray);
+            let hit = match Intersection::find_hit(&mut intersections).copied() {
+                Some(hit) => hit,
+                None => break,
+            };
+            let comps = hit.prepare_computations(&ray, &intersections);
+            let material = comps.object.get_material();
+
+            // next event estimation
+            for light in world.get_lights() {
+                radiance += throughput
+                    * material.direct_lighting(
+                        light,
+                        comps.point,
+                        comps.object_point,
+                        comps.eyev,
+                        comps.normalv,
+                        world.intensity_at(light, comps.over_point),
+                    );
+            }
+
+            // shares of the lobes, blended by the fresnel effect like in `World::shade_hit`
+            let (mut reflective, mut transparency) =
+                (material.get_reflective(), material.get_transparency());
+            if reflective > 0f64 && transparency > 0f64 {
+                let reflectance = comps.schlick();
+                reflective *= reflectance;
+                transparency *= 1f64 - reflectance;
+            }
+            let diffuse = (1f64 - reflective - transparency).max(0f64);
+            // weight of the chosen lobe divided by the probability of choosing it
+            let total = reflective + transparency + diffuse;
+
+            let choice = rng.next_f64() * total;
+            ray = if choice < reflective {
+                throughput = throughput * total;
+                Ray::new(comps.over_point, comps.reflectv)
+            } else if choice < reflective + transparency {
+                throughput = throughput * total;
+                match comps.refracted_direction() {
+                    Some(direction) => Ray::new(comps.under_point, direction),
+                    // total internal reflection
+                    None => Ray::new(comps.over_point, comps.reflectv),
+                }
+            } else if let Some(microfacet) = material.get_microfacet() {
+                let color = material.color_at(comps.object_point);
+                match microfacet.sample(color, comps.normalv, comps.eyev, rng) {
+                    Some((direction, weight)) => {
+                        throughput = throughput * weight * total;
+                        Ray::new(comps.over_point, direction)
+                    }
+                    // sampled below the surface
+                    None => break,
+                }
+            } else {
+                // cosine weighted sampling cancels the cosine term of the lambertian surface
+                let albedo = material.color_at(comps.object_point) * material.get_diffuse();
+                throughput = throughput * albedo * total;
+                Ray::new(
+                    comps.over_point,
+                    sampling::cosine_hemisphere(comps.normalv, rng),
+                )
+            };
+
+            // Russian roulette
+            if depth + 1 >= self.min_depth {
+                let survival = throughput
+                    .get_red()
+                    .max(throughput.get_green())
+                    .max(throughput.get_blue())
+                    .min(0.95);
+                if rng.next_f64() >= survival {
+                    break;
+                }
+                throughput = throughput * (1f64 / survival);
+            }
+        }
+
+        radiance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        body::{plane::Plane, sphere::Sphere, Body},
+        color::Color,
+        light::PointLight,
+        material::{microfacet::Microfacet, Material},
+        matrix::Matrix,
+        point,
+        ray::Ray,
+        tuple::Tuple,
+        utils::assert_f64_eq,
+        vector,
+        world::World,
+    };
+
+    use super::PathTracer;
+
+    fn matte(color: Color) -> Material {
+        Material::default()
+            .set_color(color)
+            .set_ambient(0.0)
+            .set_specular(0.0)
+    }
+
+    #[test]
+    fn creating_path_tracer() {
+        let tracer = PathTracer::new().set_samples(64).set_min_depth(5);
+        assert_eq!(tracer.get_samples(), 64);
+        assert_eq!(tracer.get_min_depth(), 5)
+    }
+
+    #[test]
+    fn path_missing_everything_is_black() {
+        let w = World::default();
+        let r = || Ray::new(point!(0, 0, -5), vector!(0, 1, 0));
+        assert_eq!(PathTracer::new().color_at(&w, r(), 0), Color::black())
+    }
+
+    #[test]
+    fn direct_light_matches_whitted_integrator() {
+        // light scattered off a single sphere never returns to it
+        let mut w = World::new();
+        w.add_object(Sphere::new().set_material(matte(Color::new(0.8, 1.0, 0.6))))
+            .add_light(PointLight::new(point!(-10, 10, -10), Color::white()));
+        let r = || Ray::new(point!(0, 0, -5), vector!(0.1, 0.1, 1).normalize());
+        let tracer = PathTracer::new();
+        for seed in 0..10 {
+            assert_eq!(tracer.color_at(&w, r(), seed), w.color_at(r()))
+        }
+    }
+
+    #[test]
+    fn paths_are_reproducible() {
+        let w = World::default();
+        let r = || Ray::new(point!(0, 0, -5), vector!(0, 0, 1));
+        let tracer = PathTracer::new();
+        assert_eq!(
+            format!("{:?}", tracer.color_at(&w, r(), 42)),
+            format!("{:?}", tracer.color_at(&w, r(), 42))
+        )
+    }
+
+    #[test]
+    fn color_bleeds_onto_neighbouring_surface() {
+        let mut w = World::new();
+        w.add_object(Plane::new().set_material(matte(Color::white())))
+            .add_object(
+                Sphere::new()
+                    .set_material(matte(Color::new(1.0, 0.0, 0.0)))
+                    .transform(Matrix::translation_matrix(1.5, 1.0, 0.0)),
+            )
+            .add_light(PointLight::new(point!(0, 10, 0), Color::white()));
+        let r = || Ray::new(point!(0, 1, 0), vector!(0, -1, 0));
+
+        // only direct light with the whitted integrator
+        let whitted = w.color_at(r());
+        assert_f64_eq!(whitted.get_red(), whitted.get_blue());
+
+        let tracer = PathTracer::new();
+        let n = 500;
+        let traced = (0..n).fold(Color::black(), |sum, seed| {
+            sum + tracer.color_at(&w, r(), seed) * (1.0 / n as f64)
+        });
+        assert!(traced.get_red() > whitted.get_red() + 0.05);
+        assert_f64_eq!(traced.get_blue(), whitted.get_blue())
+    }
+
+    #[test]
+    fn direct_light_on_microfacet_material_matches_whitted_integrator() {
+        let mut w = World::new();
+        w.add_object(Sphere::new().set_material(
+            matte(Color::new(0.9, 0.5, 0.1)).set_microfacet(Microfacet::new(1.0, 0.3)),
+        ))
+        .add_light(PointLight::new(point!(-10, 10, -10), Color::white()));
+        let r = || Ray::new(point!(0, 0, -5), vector!(0.1, 0.1, 1).normalize());
+        let tracer = PathTracer::new();
+        let c = w.color_at(r());
+        assert_ne!(c, Color::black());
+        for seed in 0..10 {
+            assert_eq!(tracer.color_at(&w, r(), seed), c)
+        }
+    }
+}
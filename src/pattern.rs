@@ -0,0 +1,108 @@
+use std::fmt::Debug;
+
+use crate::{color::Color, matrix::Matrix, tuple::Tuple};
+
+pub mod blend;
+pub mod checker;
+pub mod gradient;
+pub mod image_texture;
+pub mod noise_texture;
+pub mod perturbed;
+pub mod radial_gradient;
+pub mod ring;
+pub mod solid;
+pub mod stripe;
+pub mod texture_map;
+
+/// Varies the color of a material across the surface of a body.
+///
+/// Patterns are sampled in pattern space, which is object space transformed by the pattern's
+/// own transformation.
+pub trait Pattern: Debug + Send + Sync {
+    /// Color at a point in pattern space.
+    fn color_at(&self, point: Tuple) -> Color;
+    fn transform(&self, by: Matrix<4, 4>) -> Self
+    where
+        Self: Sized;
+    fn get_transformation(&self) -> Matrix<4, 4>;
+    fn get_inverse(&self) -> Matrix<4, 4>;
+    /// Color at a point in object space, or in the pattern space of the containing pattern if
+    /// nested.
+    fn color_at_object(&self, point: Tuple) -> Color {
+        self.color_at(self.get_inverse() * point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        color::Color,
+        matrix::Matrix,
+        point,
+        tuple::{Position, Tuple},
+    };
+
+    use super::Pattern;
+
+    /// Pattern returning the pattern space point as color.
+    #[derive(Debug)]
+    struct TestPattern {
+        transformation: Matrix<4, 4>,
+        inverse: Matrix<4, 4>,
+    }
+
+    impl TestPattern {
+        fn new() -> Self {
+            Self {
+                transformation: Matrix::identity_matrix(),
+                inverse: Matrix::identity_matrix(),
+            }
+        }
+    }
+
+    impl Pattern for TestPattern {
+        fn color_at(&self, point: Tuple) -> Color {
+            Color::new(point[Position::X], point[Position::Y], point[Position::Z])
+        }
+
+        fn transform(&self, by: Matrix<4, 4>) -> Self {
+            let transformation = self.transformation * by;
+            Self {
+                transformation,
+                inverse: transformation.inverse(),
+            }
+        }
+
+        fn get_transformation(&self) -> Matrix<4, 4> {
+            self.transformation
+        }
+
+        fn get_inverse(&self) -> Matrix<4, 4> {
+            self.inverse
+        }
+    }
+
+    #[test]
+    fn default_pattern_transformation() {
+        let pattern = TestPattern::new();
+        assert_eq!(pattern.get_transformation(), Matrix::identity_matrix())
+    }
+
+    #[test]
+    fn pattern_with_transformation() {
+        let pattern = TestPattern::new().transform(Matrix::scaling_matrix(2.0, 2.0, 2.0));
+        assert_eq!(
+            pattern.color_at_object(point!(2, 3, 4)),
+            Color::new(1.0, 1.5, 2.0)
+        )
+    }
+
+    #[test]
+    fn pattern_with_translation() {
+        let pattern = TestPattern::new().transform(Matrix::translation_matrix(0.5, 1.0, 1.5));
+        assert_eq!(
+            pattern.color_at_object(point!(2.5, 3, 3.5)),
+            Color::new(2.0, 2.0, 2.0)
+        )
+    }
+}
@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use crate::{color::Color, matrix::Matrix, tuple::Tuple};
+
+use super::Pattern;
+
+/// Weighted average of two patterns.
+#[derive(Clone, Debug)]
+pub struct Blend {
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    a: Arc<dyn Pattern>,
+    b: Arc<dyn Pattern>,
+    weight: f64,
+}
+
+impl Pattern for Blend {
+    fn color_at(&self, point: Tuple) -> Color {
+        self.a.color_at_object(point) * (1f64 - self.weight)
+            + self.b.color_at_object(point) * self.weight
+    }
+
+    fn transform(&self, by: Matrix<4, 4>) -> Self {
+        let transformation = self.transformation * by;
+        Self {
+            transformation,
+            inverse: transformation.inverse(),
+            ..self.clone()
+        }
+    }
+
+    fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+}
+
+impl Blend {
+    /// Blends both patterns evenly.
+    pub fn from_patterns<A: Pattern + 'static, B: Pattern + 'static>(a: A, b: B) -> Self {
+        Self {
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            a: Arc::new(a),
+            b: Arc::new(b),
+            weight: 0.5,
+        }
+    }
+
+    /// Share of the second pattern; `0` shows only the first, `1` only the second one.
+    pub fn set_weight(&self, weight: f64) -> Self {
+        if !(0f64..=1f64).contains(&weight) {
+            panic!("Weight out of range (0..=1)");
+        }
+        Self {
+            weight,
+            ..self.clone()
+        }
+    }
+
+    pub fn get_weight(&self) -> f64 {
+        self.weight
+    }
+
+    pub fn get_patterns(&self) -> (&dyn Pattern, &dyn Pattern) {
+        (self.a.as_ref(), self.b.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        color::Color,
+        matrix::{transformation::Axis, Matrix},
+        pattern::{solid::Solid, stripe::Stripe, Pattern},
+        point,
+        tuple::Tuple,
+    };
+
+    use super::Blend;
+
+    #[test]
+    fn blend_averages_patterns() {
+        let pattern = Blend::from_patterns(
+            Solid::new(Color::new(1.0, 0.0, 0.0)),
+            Solid::new(Color::new(0.0, 0.0, 1.0)),
+        );
+        assert_eq!(pattern.get_weight(), 0.5);
+        assert_eq!(pattern.color_at(point!(0, 0, 0)), Color::new(0.5, 0.0, 0.5));
+        let pattern = pattern.set_weight(0.25);
+        assert_eq!(
+            pattern.color_at(point!(0, 0, 0)),
+            Color::new(0.75, 0.0, 0.25)
+        )
+    }
+
+    #[test]
+    fn blending_crossed_stripes() {
+        let stripes = Stripe::new(Color::white(), Color::black());
+        let pattern = Blend::from_patterns(
+            stripes.clone(),
+            stripes.transform(Matrix::rotation_matrix(Axis::Y, std::f64::consts::PI / 2.0)),
+        );
+        assert_eq!(pattern.color_at(point!(0.5, 0, -0.5)), Color::white());
+        assert_eq!(
+            pattern.color_at(point!(1.5, 0, -0.5)),
+            Color::new(0.5, 0.5, 0.5)
+        );
+        assert_eq!(pattern.color_at(point!(1.5, 0, 0.5)), Color::black())
+    }
+
+    #[test]
+    #[should_panic]
+    fn blend_weight_out_of_range() {
+        let _ = Blend::from_patterns(Solid::new(Color::white()), Solid::new(Color::black()))
+            .set_weight(1.5);
+    }
+}
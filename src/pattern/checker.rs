@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use crate::{
+    color::Color,
+    matrix::Matrix,
+    tuple::{Position, Tuple},
+};
+
+use super::{solid::Solid, Pattern};
+
+/// Three dimensional checkerboard of unit cubes alternating between two colors.
+#[derive(Clone, Debug)]
+pub struct Checker {
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    a: Arc<dyn Pattern>,
+    b: Arc<dyn Pattern>,
+}
+
+impl Pattern for Checker {
+    fn color_at(&self, point: Tuple) -> Color {
+        let sum =
+            point[Position::X].floor() + point[Position::Y].floor() + point[Position::Z].floor();
+        if sum.rem_euclid(2f64) == 0f64 {
+            self.a.color_at_object(point)
+        } else {
+            self.b.color_at_object(point)
+        }
+    }
+
+    fn transform(&self, by: Matrix<4, 4>) -> Self {
+        let transformation = self.transformation * by;
+        Self {
+            transformation,
+            inverse: transformation.inverse(),
+            ..self.clone()
+        }
+    }
+
+    fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+}
+
+impl Checker {
+    pub fn new(a: Color, b: Color) -> Self {
+        Self::from_patterns(Solid::new(a), Solid::new(b))
+    }
+
+    /// Uses the colors of other patterns, sampled in the pattern space of this one.
+    pub fn from_patterns<A: Pattern + 'static, B: Pattern + 'static>(a: A, b: B) -> Self {
+        Self {
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            a: Arc::new(a),
+            b: Arc::new(b),
+        }
+    }
+
+    pub fn get_patterns(&self) -> (&dyn Pattern, &dyn Pattern) {
+        (self.a.as_ref(), self.b.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        color::Color,
+        matrix::Matrix,
+        pattern::{solid::Solid, stripe::Stripe, Pattern},
+        point,
+        tuple::Tuple,
+    };
+
+    use super::Checker;
+
+    #[test]
+    fn checkers_repeat_in_every_dimension() {
+        let pattern = Checker::new(Color::white(), Color::black());
+        let cases = [
+            (point!(0, 0, 0), Color::white()),
+            (point!(0.99, 0, 0), Color::white()),
+            (point!(1.01, 0, 0), Color::black()),
+            (point!(0, 0.99, 0), Color::white()),
+            (point!(0, 1.01, 0), Color::black()),
+            (point!(0, 0, 0.99), Color::white()),
+            (point!(0, 0, 1.01), Color::black()),
+            (point!(-0.5, 0, 0), Color::black()),
+        ];
+
+        for (point, color) in cases {
+            assert_eq!(pattern.color_at(point), color)
+        }
+    }
+
+    #[test]
+    fn checker_of_nested_stripes() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let pattern = Checker::from_patterns(
+            Stripe::new(Color::white(), Color::black())
+                .transform(Matrix::scaling_matrix(0.5, 0.5, 0.5)),
+            Solid::new(red),
+        );
+        assert_eq!(pattern.color_at(point!(0.25, 0, 0)), Color::white());
+        assert_eq!(pattern.color_at(point!(0.75, 0, 0)), Color::black());
+        assert_eq!(pattern.color_at(point!(1.25, 0, 0)), red)
+    }
+}
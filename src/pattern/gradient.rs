@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use crate::{
+    color::Color,
+    matrix::Matrix,
+    tuple::{Position, Tuple},
+};
+
+use super::{solid::Solid, Pattern};
+
+/// Linear blend from the first to the second color along the x axis, repeating at every whole
+/// number.
+#[derive(Clone, Debug)]
+pub struct Gradient {
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    a: Arc<dyn Pattern>,
+    b: Arc<dyn Pattern>,
+}
+
+impl Pattern for Gradient {
+    fn color_at(&self, point: Tuple) -> Color {
+        let x = point[Position::X];
+        let a = self.a.color_at_object(point);
+        let b = self.b.color_at_object(point);
+        a + (b - a) * (x - x.floor())
+    }
+
+    fn transform(&self, by: Matrix<4, 4>) -> Self {
+        let transformation = self.transformation * by;
+        Self {
+            transformation,
+            inverse: transformation.inverse(),
+            ..self.clone()
+        }
+    }
+
+    fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+}
+
+impl Gradient {
+    pub fn new(a: Color, b: Color) -> Self {
+        Self::from_patterns(Solid::new(a), Solid::new(b))
+    }
+
+    /// Uses the colors of other patterns, sampled in the pattern space of this one.
+    pub fn from_patterns<A: Pattern + 'static, B: Pattern + 'static>(a: A, b: B) -> Self {
+        Self {
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            a: Arc::new(a),
+            b: Arc::new(b),
+        }
+    }
+
+    pub fn get_patterns(&self) -> (&dyn Pattern, &dyn Pattern) {
+        (self.a.as_ref(), self.b.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{color::Color, pattern::Pattern, point, tuple::Tuple};
+
+    use super::Gradient;
+
+    #[test]
+    fn gradient_linearly_interpolates_between_colors() {
+        let pattern = Gradient::new(Color::white(), Color::black());
+        let cases = [
+            (0.0, Color::white()),
+            (0.25, Color::new(0.75, 0.75, 0.75)),
+            (0.5, Color::new(0.5, 0.5, 0.5)),
+            (0.75, Color::new(0.25, 0.25, 0.25)),
+        ];
+
+        for (x, color) in cases {
+            assert_eq!(pattern.color_at(point!(x, 0, 0)), color)
+        }
+    }
+}
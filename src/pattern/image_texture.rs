@@ -0,0 +1,118 @@
+use std::{fmt, io, path::Path};
+
+use crate::{canvas::Canvas, color::Color};
+
+use super::texture_map::UvPattern;
+
+/// Image sampled with bilinear filtering, `v` running from the bottom to the top row.
+pub struct ImageTexture {
+    canvas: Canvas,
+}
+
+impl ImageTexture {
+    pub fn new(canvas: Canvas) -> Self {
+        if canvas.get_width() == 0 || canvas.get_height() == 0 {
+            panic!("Image must not be empty");
+        }
+
+        Self { canvas }
+    }
+
+    /// Reads a PPM image, see `Canvas::from_ppm`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(Canvas::from_file(path)?))
+    }
+
+    pub fn get_canvas(&self) -> &Canvas {
+        &self.canvas
+    }
+}
+
+impl UvPattern for ImageTexture {
+    fn uv_color_at(&self, u: f64, v: f64) -> Color {
+        let width = self.canvas.get_width();
+        let height = self.canvas.get_height();
+
+        // pixel coordinates, the image is stored top row first
+        let x = u.clamp(0f64, 1f64) * (width - 1) as f64;
+        let y = (1f64 - v.clamp(0f64, 1f64)) * (height - 1) as f64;
+
+        let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+        let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+        let (dx, dy) = (x - x0 as f64, y - y0 as f64);
+
+        let top = self.canvas.pixel_at(x0, y0) * (1f64 - dx) + self.canvas.pixel_at(x1, y0) * dx;
+        let bottom = self.canvas.pixel_at(x0, y1) * (1f64 - dx) + self.canvas.pixel_at(x1, y1) * dx;
+        top * (1f64 - dy) + bottom * dy
+    }
+}
+
+impl fmt::Debug for ImageTexture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImageTexture")
+            .field("width", &self.canvas.get_width())
+            .field("height", &self.canvas.get_height())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        canvas::Canvas,
+        color::Color,
+        pattern::{
+            texture_map::{TextureMap, UvMapping, UvPattern},
+            Pattern,
+        },
+        point,
+        tuple::Tuple,
+    };
+
+    use super::ImageTexture;
+
+    fn gradient_image() -> ImageTexture {
+        // 3x2 image, black left to white right in the top row, red in the bottom row
+        let ppm = "P3\n3 2\n10\n0 0 0  5 5 5  10 10 10\n10 0 0  10 0 0  10 0 0\n";
+        ImageTexture::new(Canvas::from_ppm(ppm.as_bytes()).unwrap())
+    }
+
+    #[test]
+    fn image_texture_hits_pixels_exactly_at_corners() {
+        let image = gradient_image();
+        assert_eq!(image.uv_color_at(0.0, 1.0), Color::black());
+        assert_eq!(image.uv_color_at(0.5, 1.0), Color::new(0.5, 0.5, 0.5));
+        assert_eq!(image.uv_color_at(1.0, 1.0), Color::white());
+        assert_eq!(image.uv_color_at(1.0, 0.0), Color::new(1.0, 0.0, 0.0))
+    }
+
+    #[test]
+    fn image_texture_interpolates_bilinearly() {
+        let image = gradient_image();
+        assert_eq!(image.uv_color_at(0.25, 1.0), Color::new(0.25, 0.25, 0.25));
+        assert_eq!(image.uv_color_at(0.0, 0.5), Color::new(0.5, 0.0, 0.0));
+        assert_eq!(
+            image.uv_color_at(0.75, 0.5),
+            Color::new(0.875, 0.375, 0.375)
+        )
+    }
+
+    #[test]
+    #[should_panic]
+    fn image_texture_of_empty_canvas() {
+        let _ = ImageTexture::new(Canvas::new(0, 0));
+    }
+
+    #[test]
+    fn image_texture_on_plane() {
+        let pattern = TextureMap::new(gradient_image(), UvMapping::Planar);
+        assert_eq!(
+            pattern.color_at(point!(0.5, 0, 0.999999)),
+            Color::new(0.5, 0.5, 0.5)
+        );
+        assert_eq!(
+            pattern.color_at(point!(1.5, 0, 0)),
+            Color::new(1.0, 0.0, 0.0)
+        )
+    }
+}
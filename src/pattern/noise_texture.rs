@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use crate::{color::Color, matrix::Matrix, noise::Noise, tuple::Tuple};
+
+use super::{solid::Solid, Pattern};
+
+/// Blends two patterns by the value of a noise function, e.g. for clouds or, with turbulence,
+/// marble.
+#[derive(Clone, Debug)]
+pub struct NoiseTexture {
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    a: Arc<dyn Pattern>,
+    b: Arc<dyn Pattern>,
+    noise: Noise,
+    octaves: usize,
+    turbulent: bool,
+}
+
+impl Pattern for NoiseTexture {
+    fn color_at(&self, point: Tuple) -> Color {
+        let value = if self.turbulent {
+            self.noise.turbulence(point, self.octaves)
+        } else {
+            // map -1..=1 onto 0..=1
+            (self.noise.fbm(point, self.octaves) + 1f64) / 2f64
+        };
+
+        let a = self.a.color_at_object(point);
+        let b = self.b.color_at_object(point);
+        a + (b - a) * value.clamp(0f64, 1f64)
+    }
+
+    fn transform(&self, by: Matrix<4, 4>) -> Self {
+        let transformation = self.transformation * by;
+        Self {
+            transformation,
+            inverse: transformation.inverse(),
+            ..self.clone()
+        }
+    }
+
+    fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+}
+
+impl NoiseTexture {
+    pub fn new(a: Color, b: Color) -> Self {
+        Self::from_patterns(Solid::new(a), Solid::new(b))
+    }
+
+    /// Uses the colors of other patterns, sampled in the pattern space of this one.
+    pub fn from_patterns<A: Pattern + 'static, B: Pattern + 'static>(a: A, b: B) -> Self {
+        Self {
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            a: Arc::new(a),
+            b: Arc::new(b),
+            noise: Noise::Perlin,
+            octaves: 1,
+            turbulent: false,
+        }
+    }
+
+    pub fn set_noise(&self, noise: Noise) -> Self {
+        Self {
+            noise,
+            ..self.clone()
+        }
+    }
+
+    /// Number of noise layers summed up, more octaves add finer detail.
+    pub fn set_octaves(&self, octaves: usize) -> Self {
+        if octaves == 0 {
+            panic!("Octaves out of range (>=1)");
+        }
+        Self {
+            octaves,
+            ..self.clone()
+        }
+    }
+
+    /// Uses turbulence instead of fractal Brownian motion.
+    pub fn set_turbulent(&self, turbulent: bool) -> Self {
+        Self {
+            turbulent,
+            ..self.clone()
+        }
+    }
+
+    pub fn get_noise(&self) -> Noise {
+        self.noise
+    }
+
+    pub fn get_octaves(&self) -> usize {
+        self.octaves
+    }
+
+    pub fn is_turbulent(&self) -> bool {
+        self.turbulent
+    }
+
+    pub fn get_patterns(&self) -> (&dyn Pattern, &dyn Pattern) {
+        (self.a.as_ref(), self.b.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{color::Color, noise::Noise, pattern::Pattern, point, tuple::Tuple};
+
+    use super::NoiseTexture;
+
+    #[test]
+    fn noise_texture_defaults() {
+        let pattern = NoiseTexture::new(Color::white(), Color::black());
+        assert_eq!(pattern.get_noise(), Noise::Perlin);
+        assert_eq!(pattern.get_octaves(), 1);
+        assert!(!pattern.is_turbulent())
+    }
+
+    #[test]
+    fn noise_texture_is_halfway_at_lattice_points() {
+        // perlin noise vanishes at integer coordinates
+        let pattern = NoiseTexture::new(Color::white(), Color::black());
+        assert_eq!(pattern.color_at(point!(1, 2, 3)), Color::new(0.5, 0.5, 0.5));
+
+        let pattern = pattern.set_turbulent(true);
+        assert_eq!(pattern.color_at(point!(1, 2, 3)), Color::white())
+    }
+
+    #[test]
+    fn noise_texture_varies_between_colors() {
+        let pattern = NoiseTexture::new(Color::black(), Color::white())
+            .set_noise(Noise::Simplex)
+            .set_octaves(4);
+        let mut seen_dark = false;
+        let mut seen_bright = false;
+        for i in 0..200 {
+            let c = pattern.color_at(point!(i as f64 * 0.173, 0.5, i as f64 * 0.091));
+            seen_dark |= c.as_8bit().0 < 110;
+            seen_bright |= c.as_8bit().0 > 145;
+        }
+        assert!(seen_dark && seen_bright)
+    }
+
+    #[test]
+    #[should_panic]
+    fn noise_texture_needs_octaves() {
+        let _ = NoiseTexture::new(Color::white(), Color::black()).set_octaves(0);
+    }
+}
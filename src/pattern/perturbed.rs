@@ -0,0 +1,137 @@
+use std::sync::Arc;
+
+use crate::{color::Color, matrix::Matrix, noise::Noise, tuple::Tuple, vector};
+
+use super::Pattern;
+
+/// Jitters the points another pattern is sampled at by noise, breaking up straight
+/// lines, e.g. for wood or fabric.
+#[derive(Clone, Debug)]
+pub struct Perturbed {
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    pattern: Arc<dyn Pattern>,
+    scale: f64,
+    noise: Noise,
+    octaves: usize,
+}
+
+impl Pattern for Perturbed {
+    fn color_at(&self, point: Tuple) -> Color {
+        // independent noise per axis by sampling shifted points
+        let jitter = vector!(
+            self.noise.fbm(point, self.octaves),
+            self.noise.fbm(point + vector!(0, 0, 1), self.octaves),
+            self.noise.fbm(point + vector!(0, 0, 2), self.octaves)
+        );
+        self.pattern.color_at_object(point + jitter * self.scale)
+    }
+
+    fn transform(&self, by: Matrix<4, 4>) -> Self {
+        let transformation = self.transformation * by;
+        Self {
+            transformation,
+            inverse: transformation.inverse(),
+            ..self.clone()
+        }
+    }
+
+    fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+}
+
+impl Perturbed {
+    pub fn new<P: Pattern + 'static>(pattern: P) -> Self {
+        Self {
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            pattern: Arc::new(pattern),
+            scale: 0.2,
+            noise: Noise::Perlin,
+            octaves: 1,
+        }
+    }
+
+    /// Maximum distance a point is moved along each axis.
+    pub fn set_scale(&self, scale: f64) -> Self {
+        Self {
+            scale,
+            ..self.clone()
+        }
+    }
+
+    pub fn set_noise(&self, noise: Noise) -> Self {
+        Self {
+            noise,
+            ..self.clone()
+        }
+    }
+
+    /// Number of noise layers summed up, more octaves give a more ragged jitter.
+    pub fn set_octaves(&self, octaves: usize) -> Self {
+        if octaves == 0 {
+            panic!("Octaves out of range (>=1)");
+        }
+        Self {
+            octaves,
+            ..self.clone()
+        }
+    }
+
+    pub fn get_noise(&self) -> Noise {
+        self.noise
+    }
+
+    pub fn get_octaves(&self) -> usize {
+        self.octaves
+    }
+
+    pub fn get_scale(&self) -> f64 {
+        self.scale
+    }
+
+    pub fn get_pattern(&self) -> &dyn Pattern {
+        self.pattern.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        color::Color,
+        noise::Noise,
+        pattern::{gradient::Gradient, stripe::Stripe, Pattern},
+        point,
+        tuple::Tuple,
+    };
+
+    use super::Perturbed;
+
+    #[test]
+    fn perturbing_without_scale_keeps_pattern() {
+        let stripes = Stripe::new(Color::white(), Color::black());
+        let pattern = Perturbed::new(stripes.clone()).set_scale(0.0);
+        for x in [0.1, 0.45, 0.95, 1.3, -0.6] {
+            let p = point!(x, 0.3, 0.7);
+            assert_eq!(pattern.color_at(p), stripes.color_at(p))
+        }
+    }
+
+    #[test]
+    fn perturbing_moves_sampled_points() {
+        let gradient = Gradient::new(Color::black(), Color::white());
+        let pattern = Perturbed::new(gradient.clone())
+            .set_scale(0.3)
+            .set_noise(Noise::Simplex)
+            .set_octaves(3);
+        let differs = (0..20)
+            .map(|i| point!(0.05 * i as f64, 0.37, 0.61))
+            .any(|p| pattern.color_at(p) != gradient.color_at(p));
+        assert!(differs)
+    }
+}
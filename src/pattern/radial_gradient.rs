@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use crate::{
+    color::Color,
+    matrix::Matrix,
+    tuple::{Position, Tuple},
+};
+
+use super::{solid::Solid, Pattern};
+
+/// Linear blend from the first to the second color with growing distance to the y axis,
+/// repeating at every whole number.
+#[derive(Clone, Debug)]
+pub struct RadialGradient {
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    a: Arc<dyn Pattern>,
+    b: Arc<dyn Pattern>,
+}
+
+impl Pattern for RadialGradient {
+    fn color_at(&self, point: Tuple) -> Color {
+        let distance = (point[Position::X].powi(2) + point[Position::Z].powi(2)).sqrt();
+        let a = self.a.color_at_object(point);
+        let b = self.b.color_at_object(point);
+        a + (b - a) * (distance - distance.floor())
+    }
+
+    fn transform(&self, by: Matrix<4, 4>) -> Self {
+        let transformation = self.transformation * by;
+        Self {
+            transformation,
+            inverse: transformation.inverse(),
+            ..self.clone()
+        }
+    }
+
+    fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+}
+
+impl RadialGradient {
+    pub fn new(a: Color, b: Color) -> Self {
+        Self::from_patterns(Solid::new(a), Solid::new(b))
+    }
+
+    /// Uses the colors of other patterns, sampled in the pattern space of this one.
+    pub fn from_patterns<A: Pattern + 'static, B: Pattern + 'static>(a: A, b: B) -> Self {
+        Self {
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            a: Arc::new(a),
+            b: Arc::new(b),
+        }
+    }
+
+    pub fn get_patterns(&self) -> (&dyn Pattern, &dyn Pattern) {
+        (self.a.as_ref(), self.b.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{color::Color, pattern::Pattern, point, tuple::Tuple};
+
+    use super::RadialGradient;
+
+    #[test]
+    fn radial_gradient_interpolates_with_distance_to_y_axis() {
+        let pattern = RadialGradient::new(Color::white(), Color::black());
+        let cases = [
+            (point!(0, 0, 0), Color::white()),
+            (point!(0.25, 5, 0), Color::new(0.75, 0.75, 0.75)),
+            (point!(0, 0, -0.5), Color::new(0.5, 0.5, 0.5)),
+            (point!(0.6, 0, 0.8), Color::white()),
+        ];
+
+        for (point, color) in cases {
+            assert_eq!(pattern.color_at(point), color)
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use crate::{
+    color::Color,
+    matrix::Matrix,
+    tuple::{Position, Tuple},
+};
+
+use super::{solid::Solid, Pattern};
+
+/// Concentric rings around the y axis, alternating between two colors.
+#[derive(Clone, Debug)]
+pub struct Ring {
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    a: Arc<dyn Pattern>,
+    b: Arc<dyn Pattern>,
+}
+
+impl Pattern for Ring {
+    fn color_at(&self, point: Tuple) -> Color {
+        let distance = (point[Position::X].powi(2) + point[Position::Z].powi(2)).sqrt();
+        if distance.floor().rem_euclid(2f64) == 0f64 {
+            self.a.color_at_object(point)
+        } else {
+            self.b.color_at_object(point)
+        }
+    }
+
+    fn transform(&self, by: Matrix<4, 4>) -> Self {
+        let transformation = self.transformation * by;
+        Self {
+            transformation,
+            inverse: transformation.inverse(),
+            ..self.clone()
+        }
+    }
+
+    fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+}
+
+impl Ring {
+    pub fn new(a: Color, b: Color) -> Self {
+        Self::from_patterns(Solid::new(a), Solid::new(b))
+    }
+
+    /// Uses the colors of other patterns, sampled in the pattern space of this one.
+    pub fn from_patterns<A: Pattern + 'static, B: Pattern + 'static>(a: A, b: B) -> Self {
+        Self {
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            a: Arc::new(a),
+            b: Arc::new(b),
+        }
+    }
+
+    pub fn get_patterns(&self) -> (&dyn Pattern, &dyn Pattern) {
+        (self.a.as_ref(), self.b.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{color::Color, pattern::Pattern, point, tuple::Tuple};
+
+    use super::Ring;
+
+    #[test]
+    fn ring_extends_in_x_and_z() {
+        let pattern = Ring::new(Color::white(), Color::black());
+        assert_eq!(pattern.color_at(point!(0, 0, 0)), Color::white());
+        assert_eq!(pattern.color_at(point!(1, 0, 0)), Color::black());
+        assert_eq!(pattern.color_at(point!(0, 0, 1)), Color::black());
+        // 0.708 = just slightly more than sqrt(2) / 2
+        assert_eq!(pattern.color_at(point!(0.708, 0, 0.708)), Color::black())
+    }
+}
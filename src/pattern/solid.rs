@@ -0,0 +1,62 @@
+use crate::{color::Color, matrix::Matrix, tuple::Tuple};
+
+use super::Pattern;
+
+/// The same color everywhere, e.g. as input of other patterns.
+#[derive(Clone, Copy, Debug)]
+pub struct Solid {
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    color: Color,
+}
+
+impl Pattern for Solid {
+    fn color_at(&self, _point: Tuple) -> Color {
+        self.color
+    }
+
+    fn transform(&self, by: Matrix<4, 4>) -> Self {
+        let transformation = self.transformation * by;
+        Self {
+            transformation,
+            inverse: transformation.inverse(),
+            ..*self
+        }
+    }
+
+    fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+}
+
+impl Solid {
+    pub fn new(color: Color) -> Self {
+        Self {
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            color,
+        }
+    }
+
+    pub fn get_color(&self) -> Color {
+        self.color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{color::Color, pattern::Pattern, point, tuple::Tuple};
+
+    use super::Solid;
+
+    #[test]
+    fn solid_pattern_is_constant() {
+        let pattern = Solid::new(Color::new(0.2, 0.4, 0.6));
+        assert_eq!(pattern.color_at(point!(0, 0, 0)), pattern.get_color());
+        assert_eq!(pattern.color_at(point!(-3.5, 7, 1.2)), pattern.get_color())
+    }
+}
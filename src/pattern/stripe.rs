@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use crate::{
+    color::Color,
+    matrix::Matrix,
+    tuple::{Position, Tuple},
+};
+
+use super::{solid::Solid, Pattern};
+
+/// Alternates between two colors along the x axis, switching at every whole number.
+#[derive(Clone, Debug)]
+pub struct Stripe {
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    a: Arc<dyn Pattern>,
+    b: Arc<dyn Pattern>,
+}
+
+impl Pattern for Stripe {
+    fn color_at(&self, point: Tuple) -> Color {
+        if point[Position::X].floor().rem_euclid(2f64) == 0f64 {
+            self.a.color_at_object(point)
+        } else {
+            self.b.color_at_object(point)
+        }
+    }
+
+    fn transform(&self, by: Matrix<4, 4>) -> Self {
+        let transformation = self.transformation * by;
+        Self {
+            transformation,
+            inverse: transformation.inverse(),
+            ..self.clone()
+        }
+    }
+
+    fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+}
+
+impl Stripe {
+    pub fn new(a: Color, b: Color) -> Self {
+        Self::from_patterns(Solid::new(a), Solid::new(b))
+    }
+
+    /// Uses the colors of other patterns, sampled in the pattern space of this one.
+    pub fn from_patterns<A: Pattern + 'static, B: Pattern + 'static>(a: A, b: B) -> Self {
+        Self {
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            a: Arc::new(a),
+            b: Arc::new(b),
+        }
+    }
+
+    pub fn get_patterns(&self) -> (&dyn Pattern, &dyn Pattern) {
+        (self.a.as_ref(), self.b.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{color::Color, pattern::Pattern, point, tuple::Tuple};
+
+    use super::Stripe;
+
+    #[test]
+    fn creating_stripe_pattern() {
+        let pattern = Stripe::new(Color::white(), Color::black());
+        let (a, b) = pattern.get_patterns();
+        assert_eq!(a.color_at(point!(0, 0, 0)), Color::white());
+        assert_eq!(b.color_at(point!(0, 0, 0)), Color::black())
+    }
+
+    #[test]
+    fn stripe_pattern_is_constant_in_y_and_z() {
+        let pattern = Stripe::new(Color::white(), Color::black());
+        for point in [
+            point!(0, 0, 0),
+            point!(0, 1, 0),
+            point!(0, 2, 0),
+            point!(0, 0, 1),
+            point!(0, 0, 2),
+        ] {
+            assert_eq!(pattern.color_at(point), Color::white())
+        }
+    }
+
+    #[test]
+    fn stripe_pattern_alternates_in_x() {
+        let pattern = Stripe::new(Color::white(), Color::black());
+        let cases = [
+            (0.0, Color::white()),
+            (0.9, Color::white()),
+            (1.0, Color::black()),
+            (-0.1, Color::black()),
+            (-1.0, Color::black()),
+            (-1.1, Color::white()),
+        ];
+
+        for (x, color) in cases {
+            assert_eq!(pattern.color_at(point!(x, 0, 0)), color)
+        }
+    }
+}
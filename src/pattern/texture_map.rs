@@ -0,0 +1,314 @@
+use std::{f64::consts::PI, fmt::Debug, sync::Arc};
+
+use crate::{
+    color::Color,
+    matrix::Matrix,
+    tuple::{Position, Tuple},
+};
+
+use super::Pattern;
+
+/// Pattern on a two dimensional surface, addressed by `u` and `v` within `0..=1`.
+pub trait UvPattern: Debug + Send + Sync {
+    fn uv_color_at(&self, u: f64, v: f64) -> Color;
+}
+
+/// Face of the unit cube a point lies on.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CubeFace {
+    Left,
+    Right,
+    Front,
+    Back,
+    Up,
+    Down,
+}
+
+impl CubeFace {
+    /// Face the point is closest to, decided by its largest component.
+    pub fn of(point: Tuple) -> Self {
+        let (x, y, z) = (point[Position::X], point[Position::Y], point[Position::Z]);
+        let coordinate = x.abs().max(y.abs()).max(z.abs());
+
+        if coordinate == x {
+            CubeFace::Right
+        } else if coordinate == -x {
+            CubeFace::Left
+        } else if coordinate == y {
+            CubeFace::Up
+        } else if coordinate == -y {
+            CubeFace::Down
+        } else if coordinate == z {
+            CubeFace::Front
+        } else {
+            CubeFace::Back
+        }
+    }
+}
+
+/// Projects points in pattern space onto `(u, v)` coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UvMapping {
+    /// Wraps around the unit sphere, `v` running from the south to the north pole.
+    Spherical,
+    /// Repeats on the xz plane every unit.
+    Planar,
+    /// Wraps around the y axis, repeating every unit in y.
+    Cylindrical,
+    /// Covers every face of the unit cube, see `CubeFace`.
+    Cube,
+}
+
+impl UvMapping {
+    pub fn map(&self, point: Tuple) -> (f64, f64) {
+        let (x, y, z) = (point[Position::X], point[Position::Y], point[Position::Z]);
+
+        match self {
+            UvMapping::Spherical => {
+                let radius = (x * x + y * y + z * z).sqrt();
+                let phi = (y / radius).acos();
+                (Self::azimuth(x, z), 1f64 - phi / PI)
+            }
+            UvMapping::Planar => (x.rem_euclid(1f64), z.rem_euclid(1f64)),
+            UvMapping::Cylindrical => (Self::azimuth(x, z), y.rem_euclid(1f64)),
+            UvMapping::Cube => {
+                let wrap = |value: f64| (value + 1f64).rem_euclid(2f64) / 2f64;
+                match CubeFace::of(point) {
+                    CubeFace::Front => (wrap(x), wrap(y)),
+                    CubeFace::Back => (wrap(-x), wrap(y)),
+                    CubeFace::Left => (wrap(z), wrap(y)),
+                    CubeFace::Right => (wrap(-z), wrap(y)),
+                    CubeFace::Up => (wrap(x), wrap(-z)),
+                    CubeFace::Down => (wrap(x), wrap(z)),
+                }
+            }
+        }
+    }
+
+    /// Angle around the y axis mapped onto `0..1`, increasing counterclockwise seen from above.
+    fn azimuth(x: f64, z: f64) -> f64 {
+        let theta = x.atan2(z);
+        1f64 - (theta / (2f64 * PI) + 0.5)
+    }
+}
+
+/// Two dimensional checkerboard with `width` times `height` squares.
+#[derive(Clone, Copy, Debug)]
+pub struct UvChecker {
+    width: f64,
+    height: f64,
+    a: Color,
+    b: Color,
+}
+
+impl UvChecker {
+    pub fn new(width: f64, height: f64, a: Color, b: Color) -> Self {
+        Self {
+            width,
+            height,
+            a,
+            b,
+        }
+    }
+}
+
+impl UvPattern for UvChecker {
+    fn uv_color_at(&self, u: f64, v: f64) -> Color {
+        let u = (u * self.width).floor();
+        let v = (v * self.height).floor();
+        if (u + v).rem_euclid(2f64) == 0f64 {
+            self.a
+        } else {
+            self.b
+        }
+    }
+}
+
+/// Wraps a `UvPattern` around a body using a `UvMapping`.
+#[derive(Clone, Debug)]
+pub struct TextureMap {
+    transformation: Matrix<4, 4>,
+    inverse: Matrix<4, 4>,
+    uv_pattern: Arc<dyn UvPattern>,
+    mapping: UvMapping,
+}
+
+impl Pattern for TextureMap {
+    fn color_at(&self, point: Tuple) -> Color {
+        let (u, v) = self.mapping.map(point);
+        self.uv_pattern.uv_color_at(u, v)
+    }
+
+    fn transform(&self, by: Matrix<4, 4>) -> Self {
+        let transformation = self.transformation * by;
+        Self {
+            transformation,
+            inverse: transformation.inverse(),
+            ..self.clone()
+        }
+    }
+
+    fn get_transformation(&self) -> Matrix<4, 4> {
+        self.transformation
+    }
+
+    fn get_inverse(&self) -> Matrix<4, 4> {
+        self.inverse
+    }
+}
+
+impl TextureMap {
+    pub fn new<P: UvPattern + 'static>(uv_pattern: P, mapping: UvMapping) -> Self {
+        Self {
+            transformation: Matrix::identity_matrix(),
+            inverse: Matrix::identity_matrix(),
+            uv_pattern: Arc::new(uv_pattern),
+            mapping,
+        }
+    }
+
+    pub fn get_mapping(&self) -> UvMapping {
+        self.mapping
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{color::Color, pattern::Pattern, point, tuple::Tuple, utils::assert_f64_eq};
+
+    use super::{CubeFace, TextureMap, UvChecker, UvMapping, UvPattern};
+
+    #[test]
+    fn checker_pattern_in_2d() {
+        let checkers = UvChecker::new(2.0, 2.0, Color::black(), Color::white());
+        let cases = [
+            (0.0, 0.0, Color::black()),
+            (0.5, 0.0, Color::white()),
+            (0.0, 0.5, Color::white()),
+            (0.5, 0.5, Color::black()),
+            (1.0, 1.0, Color::black()),
+        ];
+
+        for (u, v, color) in cases {
+            assert_eq!(checkers.uv_color_at(u, v), color)
+        }
+    }
+
+    #[test]
+    fn spherical_mapping_on_3d_point() {
+        let cases = [
+            (point!(0, 0, -1), 0.0, 0.5),
+            (point!(1, 0, 0), 0.25, 0.5),
+            (point!(0, 0, 1), 0.5, 0.5),
+            (point!(-1, 0, 0), 0.75, 0.5),
+            (point!(0, 1, 0), 0.5, 1.0),
+            (point!(0, -1, 0), 0.5, 0.0),
+            (point!(2f64.sqrt() / 2.0, 2f64.sqrt() / 2.0, 0), 0.25, 0.75),
+        ];
+
+        for (point, u, v) in cases {
+            let (mapped_u, mapped_v) = UvMapping::Spherical.map(point);
+            assert_f64_eq!(mapped_u, u);
+            assert_f64_eq!(mapped_v, v)
+        }
+    }
+
+    #[test]
+    fn planar_mapping_on_3d_point() {
+        let cases = [
+            (point!(0.25, 0, 0.5), 0.25, 0.5),
+            (point!(0.25, 0, -0.25), 0.25, 0.75),
+            (point!(0.25, 0.5, -0.25), 0.25, 0.75),
+            (point!(1.25, 0, 0.5), 0.25, 0.5),
+            (point!(0.25, 0, -1.75), 0.25, 0.25),
+            (point!(1, 0, -1), 0.0, 0.0),
+        ];
+
+        for (point, u, v) in cases {
+            let (mapped_u, mapped_v) = UvMapping::Planar.map(point);
+            assert_f64_eq!(mapped_u, u);
+            assert_f64_eq!(mapped_v, v)
+        }
+    }
+
+    #[test]
+    fn cylindrical_mapping_on_3d_point() {
+        let cases = [
+            (point!(0, 0, -1), 0.0, 0.0),
+            (point!(0, 0.5, -1), 0.0, 0.5),
+            (point!(0, 1, -1), 0.0, 0.0),
+            (
+                point!(2f64.sqrt() / 2.0, 0.5, -2f64.sqrt() / 2.0),
+                0.125,
+                0.5,
+            ),
+            (point!(1, 0.5, 0), 0.25, 0.5),
+            (point!(0, -0.25, 1), 0.5, 0.75),
+            (point!(-1, 1.25, 0), 0.75, 0.25),
+        ];
+
+        for (point, u, v) in cases {
+            let (mapped_u, mapped_v) = UvMapping::Cylindrical.map(point);
+            assert_f64_eq!(mapped_u, u);
+            assert_f64_eq!(mapped_v, v)
+        }
+    }
+
+    #[test]
+    fn identifying_face_of_cube_from_point() {
+        let cases = [
+            (point!(-1, 0.5, -0.25), CubeFace::Left),
+            (point!(1.1, -0.75, 0.8), CubeFace::Right),
+            (point!(0.1, 0.6, 0.9), CubeFace::Front),
+            (point!(-0.7, 0, -2), CubeFace::Back),
+            (point!(0.5, 1, 0.9), CubeFace::Up),
+            (point!(-0.2, -1.3, 1.1), CubeFace::Down),
+        ];
+
+        for (point, face) in cases {
+            assert_eq!(CubeFace::of(point), face)
+        }
+    }
+
+    #[test]
+    fn cube_mapping_on_faces() {
+        let cases = [
+            (point!(-0.5, 0.5, 1), 0.25, 0.75),
+            (point!(0.5, -0.5, 1), 0.75, 0.25),
+            (point!(0.5, 0.5, -1), 0.25, 0.75),
+            (point!(-0.5, 0.5, -1), 0.75, 0.75),
+            (point!(-1, 0.5, -0.5), 0.25, 0.75),
+            (point!(1, 0.5, 0.5), 0.25, 0.75),
+            (point!(-0.5, 1, -0.5), 0.25, 0.75),
+            (point!(-0.5, -1, 0.5), 0.25, 0.75),
+        ];
+
+        for (point, u, v) in cases {
+            let (mapped_u, mapped_v) = UvMapping::Cube.map(point);
+            assert_f64_eq!(mapped_u, u);
+            assert_f64_eq!(mapped_v, v)
+        }
+    }
+
+    #[test]
+    fn using_texture_map_with_spherical_mapping() {
+        let checkers = UvChecker::new(16.0, 8.0, Color::black(), Color::white());
+        let pattern = TextureMap::new(checkers, UvMapping::Spherical);
+        let cases = [
+            (point!(0.4315, 0.4670, 0.7719), Color::white()),
+            (point!(-0.9654, 0.2552, -0.0534), Color::black()),
+            (point!(0.1039, 0.7090, 0.6975), Color::white()),
+            (point!(-0.4986, -0.7856, -0.3663), Color::black()),
+            (point!(-0.0317, -0.9395, 0.3411), Color::black()),
+            (point!(0.4809, -0.7721, 0.4154), Color::black()),
+            (point!(0.0285, -0.9612, -0.2745), Color::black()),
+            (point!(-0.5734, -0.2162, -0.7903), Color::white()),
+            (point!(0.7688, -0.1470, 0.6223), Color::black()),
+            (point!(-0.7652, 0.2175, 0.6060), Color::black()),
+        ];
+
+        for (point, color) in cases {
+            assert_eq!(pattern.color_at(point), color)
+        }
+    }
+}
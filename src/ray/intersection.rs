@@ -1,34 +1,168 @@
-use crate::{body::Body, tuple::Tuple, utils::EPSILON};
+use crate::{
+    body::Body,
+    material::refractive_index::VACUUM,
+    matrix::Matrix,
+    tuple::{Position, Tuple},
+    utils::EPSILON,
+};
 
 use super::Ray;
 
 #[derive(Clone, Copy)]
-pub(crate) struct Intersection<'a> {
+pub struct Intersection<'a> {
     t: f64,
     object: &'a dyn Body,
+    uv: Option<(f64, f64)>,
+    // inverse transformation of all groups containing the object, `None` if not part of a group
+    parent_inverse: Option<Matrix<4, 4>>,
 }
 
 impl<'a> Intersection<'a> {
     pub fn new(t: f64, object: &'a dyn Body) -> Self {
-        Self { t, object }
+        Self {
+            t,
+            object,
+            uv: None,
+            parent_inverse: None,
+        }
+    }
+
+    /// Intersection that also records where on the surface of the object the hit occurred,
+    /// e.g. the barycentric coordinates on a triangle.
+    pub fn new_with_uv(t: f64, object: &'a dyn Body, u: f64, v: f64) -> Self {
+        Self {
+            t,
+            object,
+            uv: Some((u, v)),
+            parent_inverse: None,
+        }
+    }
+
+    /// Places the intersected object inside a group whose transformation has the given
+    /// `inverse`. Groups call this on every intersection of their children, innermost first.
+    pub fn with_parent(&self, inverse: Matrix<4, 4>) -> Self {
+        Self {
+            parent_inverse: Some(match self.parent_inverse {
+                Some(parent_inverse) => parent_inverse * inverse,
+                None => inverse,
+            }),
+            ..*self
+        }
+    }
+
+    /// Converts a world space point into the space the object's own transformation applies to.
+    pub fn world_to_object(&self, point: Tuple) -> Tuple {
+        match self.parent_inverse {
+            Some(parent_inverse) => parent_inverse * point,
+            None => point,
+        }
+    }
+
+    /// Converts a normal from the space the object's own transformation applies to back into
+    /// world space.
+    pub fn normal_to_world(&self, normal: Tuple) -> Tuple {
+        match self.parent_inverse {
+            Some(parent_inverse) => {
+                let normal = parent_inverse.transpose() * normal;
+                // WORKAROUND! page 82
+                Tuple::new_vec(
+                    normal[Position::X],
+                    normal[Position::Y],
+                    normal[Position::Z],
+                )
+                .normalize()
+            }
+            None => normal,
+        }
+    }
+
+    /// Converts a gradient of a function over object space into world space. Gradients
+    /// transform like normals, but keep their length.
+    pub fn gradient_to_world(&self, gradient: Tuple) -> Tuple {
+        let gradient = self.object.get_inverse_transpose() * gradient;
+        let gradient = match self.parent_inverse {
+            Some(parent_inverse) => parent_inverse.transpose() * gradient,
+            None => gradient,
+        };
+        Tuple::new_vec(
+            gradient[Position::X],
+            gradient[Position::Y],
+            gradient[Position::Z],
+        )
     }
 
-    pub fn prepare_computations(&self, ray: &Ray) -> Computations {
+    /// Precomputes everything needed for shading this intersection.
+    ///
+    /// `intersections` are all intersections along the ray, sorted by `t`; they determine the
+    /// refractive indices on both sides of the hit.
+    pub(crate) fn prepare_computations(
+        &self,
+        ray: &Ray,
+        intersections: &[Intersection],
+    ) -> Computations<'_> {
         let point = ray.position(self.t);
-        let normalv = self.object.normal_at(point);
+        let object_point = self.object.get_inverse() * self.world_to_object(point);
+        let normalv = self.normal_to_world(
+            self.object
+                .normal_at_intersection(self.world_to_object(point), self),
+        );
+        let normalv = match self.object.get_material().get_normal_perturbation() {
+            Some(perturbation) => perturbation.perturb(object_point, normalv, self),
+            None => normalv,
+        };
         let eyev = -ray.get_direction();
         let inside = normalv.dot(eyev) < 0f64;
+        let normalv = if inside { -normalv } else { normalv };
         let over_point = point + normalv * EPSILON;
+        let under_point = point - normalv * EPSILON;
+        let (n1, n2) = self.refractive_indices(intersections);
         Computations {
             // TODO: copy just for convenience, consider ref
             t: self.t,
             inside,
             object: self.object,
             point,
+            object_point,
             over_point,
+            under_point,
             eyev,
-            normalv: if inside { -normalv } else { normalv },
+            normalv,
+            reflectv: ray.get_direction().reflect_at(normalv),
+            n1,
+            n2,
+        }
+    }
+
+    /// Refractive indices of the media the ray exits (`n1`) and enters (`n2`) at this
+    /// intersection. Outside of every body the index of a vacuum is assumed.
+    fn refractive_indices(&self, intersections: &[Intersection]) -> (f64, f64) {
+        // bodies the ray is currently inside of, most recently entered last
+        let mut containers: Vec<&dyn Body> = vec![];
+        let refractive_index = |containers: &Vec<&dyn Body>| match containers.last() {
+            Some(object) => object.get_material().get_refractive_index(),
+            None => VACUUM,
+        };
+
+        for intersection in intersections {
+            let is_hit =
+                intersection.t == self.t && intersection.object.get_id() == self.object.get_id();
+            let n1 = refractive_index(&containers);
+
+            let id = intersection.object.get_id();
+            match containers.iter().position(|object| object.get_id() == id) {
+                Some(index) => {
+                    containers.remove(index);
+                }
+                None => containers.push(intersection.object),
+            }
+
+            if is_hit {
+                return (n1, refractive_index(&containers));
+            }
         }
+
+        // not part of the given intersections
+        (VACUUM, VACUUM)
     }
 
     pub fn get_t(&self) -> f64 {
@@ -39,7 +173,13 @@ impl<'a> Intersection<'a> {
         self.object
     }
 
-    pub fn find_hit(intersections: &'a mut Vec<Intersection>) -> Option<&'a Intersection<'a>> {
+    pub fn get_uv(&self) -> Option<(f64, f64)> {
+        self.uv
+    }
+
+    pub fn find_hit<'b>(
+        intersections: &'b mut Vec<Intersection<'a>>,
+    ) -> Option<&'b Intersection<'a>> {
         // TODO: sort necessary here? should already be sorted
         intersections.sort_by(|a, b| a.get_t().total_cmp(&b.get_t()));
         intersections.iter().find(|elm| elm.get_t() >= 0.0)
@@ -52,9 +192,50 @@ pub(crate) struct Computations<'a> {
     pub inside: bool,
     pub object: &'a dyn Body,
     pub point: Tuple,
+    // `point` in object space, e.g. for sampling patterns
+    pub object_point: Tuple,
     pub over_point: Tuple,
+    pub under_point: Tuple,
     pub eyev: Tuple,
     pub normalv: Tuple,
+    pub reflectv: Tuple,
+    pub n1: f64,
+    pub n2: f64,
+}
+
+impl Computations<'_> {
+    /// Direction of the ray refracted at the hit by Snell's law, `None` under total internal
+    /// reflection.
+    pub fn refracted_direction(&self) -> Option<Tuple> {
+        let n_ratio = self.n1 / self.n2;
+        let cos_i = self.eyev.dot(self.normalv);
+        let sin2_t = n_ratio.powi(2) * (1f64 - cos_i.powi(2));
+        if sin2_t > 1f64 {
+            return None;
+        }
+
+        let cos_t = (1f64 - sin2_t).sqrt();
+        Some(self.normalv * (n_ratio * cos_i - cos_t) - self.eyev * n_ratio)
+    }
+
+    /// Schlick's approximation of the Fresnel reflectance, i.e. the share of light reflected
+    /// rather than refracted at the hit.
+    pub fn schlick(&self) -> f64 {
+        let mut cos = self.eyev.dot(self.normalv);
+
+        // total internal reflection is only possible when entering a less dense medium
+        if self.n1 > self.n2 {
+            let n_ratio = self.n1 / self.n2;
+            let sin2_t = n_ratio.powi(2) * (1f64 - cos.powi(2));
+            if sin2_t > 1f64 {
+                return 1f64;
+            }
+            cos = (1f64 - sin2_t).sqrt();
+        }
+
+        let r0 = ((self.n1 - self.n2) / (self.n1 + self.n2)).powi(2);
+        r0 + (1f64 - r0) * (1f64 - cos).powi(5)
+    }
 }
 
 // TODO: check page 64 - aggregating; substitute with `vec![]` at the moment
@@ -63,6 +244,7 @@ pub(crate) struct Computations<'a> {
 mod tests {
     use crate::{
         body::{sphere::Sphere, Body},
+        material::Material,
         matrix::Matrix,
         point,
         ray::Ray,
@@ -89,6 +271,14 @@ mod tests {
         assert_eq!(i.get_object().get_id(), s.get_id())
     }
 
+    #[test]
+    fn intersection_can_encapsulate_u_and_v() {
+        let s = Sphere::new();
+        let i = Intersection::new_with_uv(3.5, &s, 0.2, 0.4);
+        assert_eq!(i.get_uv(), Some((0.2, 0.4)));
+        assert_eq!(Intersection::new(3.5, &s).get_uv(), None)
+    }
+
     #[test]
     fn hit_when_all_positive_t() {
         let s = Sphere::new();
@@ -136,7 +326,7 @@ mod tests {
         let r = Ray::new(point!(0, 0, -5), vector!(0, 0, 1));
         let shape = Sphere::new();
         let i = Intersection::new(4.0, &shape);
-        let comps = i.prepare_computations(&r);
+        let comps = i.prepare_computations(&r, &[i]);
         assert_f64_eq!(comps.t, i.get_t());
         assert_eq!(comps.point, point!(0, 0, -1));
         assert_eq!(comps.eyev, vector!(0, 0, -1));
@@ -148,7 +338,7 @@ mod tests {
         let r = Ray::new(point!(0, 0, -5), vector!(0, 0, 1));
         let shape = Sphere::new();
         let i = Intersection::new(4.0, &shape);
-        let comps = i.prepare_computations(&r);
+        let comps = i.prepare_computations(&r, &[i]);
         assert!(!comps.inside)
     }
 
@@ -157,7 +347,7 @@ mod tests {
         let r = Ray::new(Tuple::point_origin(), vector!(0, 0, 1));
         let shape = Sphere::new();
         let i = Intersection::new(1.0, &shape);
-        let comps = i.prepare_computations(&r);
+        let comps = i.prepare_computations(&r, &[i]);
         assert_eq!(comps.point, point!(0, 0, 1));
         assert_eq!(comps.eyev, vector!(0, 0, -1));
         assert!(comps.inside);
@@ -170,8 +360,100 @@ mod tests {
         let r = Ray::new(point!(0, 0, -5), vector!(0, 0, 1));
         let shape = Sphere::new().transform(Matrix::translation_matrix(0.0, 0.0, 1.0));
         let i = Intersection::new(5.0, &shape);
-        let comps = i.prepare_computations(&r);
+        let comps = i.prepare_computations(&r, &[i]);
         assert!(comps.over_point[Position::Z] < -EPSILON / 2.0);
         assert!(comps.point[Position::Z] > comps.over_point[Position::Z])
     }
+
+    fn glass_sphere() -> Sphere {
+        Sphere::new().set_material(
+            Material::default()
+                .set_transparency(1.0)
+                .set_refractive_index(1.5),
+        )
+    }
+
+    #[test]
+    fn finding_n1_and_n2_at_various_intersections() {
+        let glass = |refractive_index| {
+            glass_sphere()
+                .get_material()
+                .set_refractive_index(refractive_index)
+        };
+        let a = Sphere::new()
+            .set_material(glass(1.5))
+            .transform(Matrix::scaling_matrix(2.0, 2.0, 2.0));
+        let b = Sphere::new()
+            .set_material(glass(2.0))
+            .transform(Matrix::translation_matrix(0.0, 0.0, -0.25));
+        let c = Sphere::new()
+            .set_material(glass(2.5))
+            .transform(Matrix::translation_matrix(0.0, 0.0, 0.25));
+        let r = Ray::new(point!(0, 0, -4), vector!(0, 0, 1));
+        let xs = [
+            Intersection::new(2.0, &a),
+            Intersection::new(2.75, &b),
+            Intersection::new(3.25, &c),
+            Intersection::new(4.75, &b),
+            Intersection::new(5.25, &c),
+            Intersection::new(6.0, &a),
+        ];
+        let expected = [
+            (1.0, 1.5),
+            (1.5, 2.0),
+            (2.0, 2.5),
+            (2.5, 2.5),
+            (2.5, 1.5),
+            (1.5, 1.0),
+        ];
+
+        for (i, (n1, n2)) in xs.iter().zip(expected) {
+            let comps = i.prepare_computations(&r, &xs);
+            assert_f64_eq!(comps.n1, n1);
+            assert_f64_eq!(comps.n2, n2)
+        }
+    }
+
+    #[test]
+    fn under_point_is_offset_below_surface() {
+        let r = Ray::new(point!(0, 0, -5), vector!(0, 0, 1));
+        let shape = glass_sphere().transform(Matrix::translation_matrix(0.0, 0.0, 1.0));
+        let i = Intersection::new(5.0, &shape);
+        let comps = i.prepare_computations(&r, &[i]);
+        assert!(comps.under_point[Position::Z] > EPSILON / 2.0);
+        assert!(comps.point[Position::Z] < comps.under_point[Position::Z])
+    }
+
+    #[test]
+    fn schlick_approximation_under_total_internal_reflection() {
+        let shape = glass_sphere();
+        let r = Ray::new(point!(0, 0, 2f64.sqrt() / 2.0), vector!(0, 1, 0));
+        let xs = [
+            Intersection::new(-2f64.sqrt() / 2.0, &shape),
+            Intersection::new(2f64.sqrt() / 2.0, &shape),
+        ];
+        let comps = xs[1].prepare_computations(&r, &xs);
+        assert_f64_eq!(comps.schlick(), 1.0)
+    }
+
+    #[test]
+    fn schlick_approximation_with_perpendicular_viewing_angle() {
+        let shape = glass_sphere();
+        let r = Ray::new(Tuple::point_origin(), vector!(0, 1, 0));
+        let xs = [
+            Intersection::new(-1.0, &shape),
+            Intersection::new(1.0, &shape),
+        ];
+        let comps = xs[1].prepare_computations(&r, &xs);
+        assert_f64_eq!(comps.schlick(), 0.04)
+    }
+
+    #[test]
+    fn schlick_approximation_with_small_angle_and_n2_greater_n1() {
+        let shape = glass_sphere();
+        let r = Ray::new(point!(0, 0.99, -2), vector!(0, 0, 1));
+        let xs = [Intersection::new(1.8589, &shape)];
+        let comps = xs[0].prepare_computations(&r, &xs);
+        assert_f64_eq!(comps.schlick(), 0.48873)
+    }
 }
@@ -0,0 +1,116 @@
+use std::f64::consts::PI;
+
+use crate::tuple::Tuple;
+
+/// Small, seedable pseudo random number generator (splitmix64).
+///
+/// Renders seed it per pixel and sample, so results are reproducible regardless of how the
+/// work is spread over threads.
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniformly distributed value in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        // top 53 bits fill the mantissa
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Two unit vectors completing `normal` to an orthonormal basis.
+pub(crate) fn orthonormal_basis(normal: Tuple) -> (Tuple, Tuple) {
+    let helper = if normal.dot(Tuple::new_vec(1.0, 0.0, 0.0)).abs() > 0.9 {
+        Tuple::new_vec(0.0, 1.0, 0.0)
+    } else {
+        Tuple::new_vec(1.0, 0.0, 0.0)
+    };
+    let tangent = normal.cross(helper).normalize();
+    (tangent, normal.cross(tangent))
+}
+
+/// Direction in the hemisphere around `normal`, with a density proportional to the cosine of
+/// the angle to the normal.
+pub(crate) fn cosine_hemisphere(normal: Tuple, rng: &mut Rng) -> Tuple {
+    let (tangent, bitangent) = orthonormal_basis(normal);
+    let r = rng.next_f64().sqrt();
+    let phi = 2f64 * PI * rng.next_f64();
+    let z = (1f64 - r * r).max(0f64).sqrt();
+
+    (tangent * (r * phi.cos()) + bitangent * (r * phi.sin()) + normal * z).normalize()
+}
+
+/// Microfacet normal around `normal`, distributed by the GGX distribution of roughness
+/// `alpha` weighted by its cosine to the normal.
+pub(crate) fn ggx_half_vector(normal: Tuple, alpha: f64, rng: &mut Rng) -> Tuple {
+    let (tangent, bitangent) = orthonormal_basis(normal);
+    let u = rng.next_f64();
+    let phi = 2f64 * PI * rng.next_f64();
+    let cos2_theta = (1f64 - u) / (1f64 + (alpha.powi(2) - 1f64) * u);
+    let cos_theta = cos2_theta.sqrt();
+    let sin_theta = (1f64 - cos2_theta).max(0f64).sqrt();
+
+    (tangent * (sin_theta * phi.cos()) + bitangent * (sin_theta * phi.sin()) + normal * cos_theta)
+        .normalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tuple::Tuple, utils::float_eq, vector};
+
+    use super::{cosine_hemisphere, orthonormal_basis, Rng};
+
+    #[test]
+    fn random_numbers_are_reproducible_and_in_range() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..1000 {
+            let x = a.next_f64();
+            assert_eq!(x, b.next_f64());
+            assert!((0.0..1.0).contains(&x))
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64())
+    }
+
+    #[test]
+    fn basis_is_orthonormal() {
+        for normal in [
+            vector!(0, 1, 0),
+            vector!(1, 0, 0),
+            vector!(1, -2, 3).normalize(),
+        ] {
+            let (t, b) = orthonormal_basis(normal);
+            assert!(float_eq(t.dot(normal), 0.0));
+            assert!(float_eq(b.dot(normal), 0.0));
+            assert!(float_eq(t.dot(b), 0.0));
+            assert!(float_eq(t.dot(t), 1.0));
+            assert!(float_eq(b.dot(b), 1.0))
+        }
+    }
+
+    #[test]
+    fn hemisphere_samples_are_cosine_weighted() {
+        let normal = vector!(0, 0, -1);
+        let mut rng = Rng::new(7);
+        let n = 20000;
+        let mut mean_cos = 0.0;
+        for _ in 0..n {
+            let d = cosine_hemisphere(normal, &mut rng);
+            assert!(float_eq(d.dot(d), 1.0));
+            assert!(d.dot(normal) >= 0.0);
+            mean_cos += d.dot(normal) / n as f64;
+        }
+        // expected value of the cosine is 2/3
+        assert!((mean_cos - 2.0 / 3.0).abs() < 0.01)
+    }
+}
@@ -1,7 +1,11 @@
+use std::sync::OnceLock;
+
 use crate::{
     body::{sphere::Sphere, Body},
+    bounds::BoundingBox,
+    bvh::Bvh,
     color::Color,
-    light::{self, Light, PointLight},
+    light::{self, Light, LightSample, PointLight},
     material::Material,
     matrix::Matrix,
     ray::{
@@ -11,9 +15,15 @@ use crate::{
     tuple::Tuple,
 };
 
+/// Number of times a ray may bounce off reflective surfaces by default.
+pub const DEFAULT_MAX_DEPTH: usize = 5;
+
 pub struct World<'a> {
     lights: Vec<Box<dyn Light + 'a>>,
     objects: Vec<Box<dyn Body + 'a>>,
+    // built on first intersection, reset whenever objects are added
+    bvh: OnceLock<Bvh>,
+    max_depth: usize,
 }
 
 impl<'a> World<'a> {
@@ -21,6 +31,8 @@ impl<'a> World<'a> {
         Self {
             lights: vec![],
             objects: vec![],
+            bvh: OnceLock::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
         }
     }
 
@@ -42,18 +54,35 @@ impl<'a> World<'a> {
                 ),
                 Box::new(Sphere::new().transform(Matrix::scaling_matrix(0.5, 0.5, 0.5))),
             ],
+            bvh: OnceLock::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
         }
     }
 
     pub fn color_at(&self, ray: Ray) -> Color {
+        self.color_at_depth(ray, self.max_depth)
+    }
+
+    /// Color seen along the ray, following at most `remaining` further bounces.
+    fn color_at_depth(&self, ray: Ray, remaining: usize) -> Color {
         let mut intersections = self.intersect(&ray);
-        let hit = Intersection::find_hit(&mut intersections);
+        let hit = Intersection::find_hit(&mut intersections).copied();
         match hit {
             None => Color::black(),
-            Some(hit) => self.shade_hit(hit.prepare_computations(&ray)),
+            Some(hit) => self.shade_hit(hit.prepare_computations(&ray, &intersections), remaining),
         }
     }
 
+    pub fn get_max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Limits how often rays bounce off reflective surfaces, e.g. between two mirrors.
+    pub fn set_max_depth(&mut self, max_depth: usize) -> &mut Self {
+        self.max_depth = max_depth;
+        self
+    }
+
     pub fn get_objects(&self) -> Vec<&dyn Body> {
         self.objects.iter().map(|elm| elm.as_ref()).collect()
     }
@@ -61,6 +90,7 @@ impl<'a> World<'a> {
     // TODO: rather mutate in place
     pub fn add_object<O: Body + 'a>(&mut self, object: O) -> &mut Self {
         self.objects.push(Box::new(object));
+        self.bvh = OnceLock::new();
         self
     }
 
@@ -80,72 +110,125 @@ impl<'a> World<'a> {
         self
     }
 
-    fn intersect(&self, ray: &Ray) -> Vec<Intersection> {
+    pub(crate) fn intersect(&self, ray: &Ray) -> Vec<Intersection> {
         let mut intersections = vec![];
 
-        for object in self.get_objects() {
-            if let Some(intersection) = object.intersect(&ray) {
-                intersections.extend(intersection);
-            }
+        for index in self.get_bvh().candidates(ray) {
+            intersections.extend(self.objects[index].intersect(ray));
         }
 
         intersections.sort_by(|a, b| a.get_t().total_cmp(&b.get_t()));
         intersections
     }
 
-    fn shade_hit(&self, precomputations: Computations) -> Color {
+    fn get_bvh(&self) -> &Bvh {
+        self.bvh.get_or_init(|| {
+            let bounds: Vec<BoundingBox> = self
+                .objects
+                .iter()
+                .map(|object| object.get_parent_space_bounds())
+                .collect();
+            Bvh::build(&bounds)
+        })
+    }
+
+    fn shade_hit(&self, precomputations: Computations, remaining: usize) -> Color {
         let mut color = Color::black();
 
+        let material = precomputations.object.get_material();
         for light in self.get_lights() {
-            color += precomputations.object.get_material().lighting(
+            color += material.lighting(
                 light,
                 precomputations.point,
+                precomputations.object_point,
                 precomputations.eyev,
                 precomputations.normalv,
-                self.is_shadowed(precomputations.over_point),
+                self.intensity_at(light, precomputations.over_point),
             )
         }
 
-        color
+        let reflected = self.reflected_color(&precomputations, remaining);
+        let refracted = self.refracted_color(&precomputations, remaining);
+
+        if material.get_reflective() > 0f64 && material.get_transparency() > 0f64 {
+            // blend by the fresnel effect, glass reflects more at grazing angles
+            let reflectance = precomputations.schlick();
+            color + reflected * reflectance + refracted * (1f64 - reflectance)
+        } else {
+            color + reflected + refracted
+        }
     }
 
-    // TESTME: test multiple sources
-    fn is_shadowed(&self, point: Tuple) -> bool {
-        for light in self.get_lights() {
-            let v = light.get_position() - point;
-            let distance = v.magnitude();
-            let direction = v.normalize();
-
-            let ray = Ray::new(point, direction);
-            let mut intersections = self.intersect(&ray);
-
-            let hit = Intersection::find_hit(&mut intersections);
-            if let Some(hit) = hit {
-                if hit.get_t() < distance {
-                    return true;
-                }
+    fn reflected_color(&self, precomputations: &Computations, remaining: usize) -> Color {
+        let reflective = precomputations.object.get_material().get_reflective();
+        if remaining == 0 || reflective == 0f64 {
+            return Color::black();
+        }
+
+        let ray = Ray::new(precomputations.over_point, precomputations.reflectv);
+        self.color_at_depth(ray, remaining - 1) * reflective
+    }
+
+    fn refracted_color(&self, precomputations: &Computations, remaining: usize) -> Color {
+        let transparency = precomputations.object.get_material().get_transparency();
+        if remaining == 0 || transparency == 0f64 {
+            return Color::black();
+        }
+
+        match precomputations.refracted_direction() {
+            Some(direction) => {
+                let ray = Ray::new(precomputations.under_point, direction);
+                self.color_at_depth(ray, remaining - 1) * transparency
             }
+            // total internal reflection
+            None => Color::black(),
+        }
+    }
+
+    /// Fraction of `light` reaching `point`, i.e. of its samples not shadowed by any object.
+    /// Every light is evaluated on its own, a point shadowed from one light may still be lit
+    /// by another.
+    pub fn intensity_at(&self, light: &dyn Light, point: Tuple) -> f64 {
+        let samples = light.get_samples(point);
+        let lit = samples
+            .iter()
+            .filter(|sample| !self.is_shadowed(point, sample))
+            .count();
+        lit as f64 / samples.len() as f64
+    }
+
+    /// Checks whether an object blocks the line of sight from `point` to the sampled light.
+    /// Shadow rays of lights without position extend to infinity.
+    pub(crate) fn is_shadowed(&self, point: Tuple, sample: &LightSample) -> bool {
+        let ray = Ray::new(point, sample.direction);
+        let mut intersections = self.intersect(&ray);
+
+        let hit = Intersection::find_hit(&mut intersections);
+        match hit {
+            Some(hit) => hit.get_t() < sample.distance,
+            None => false,
         }
-        // if world has no light
-        false
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::OnceLock;
+
     use crate::{
-        body::{sphere::Sphere, Body},
+        body::{cube::Cube, group::Group, plane::Plane, sphere::Sphere, Body},
         color::Color,
-        light::{Light, PointLight},
+        light::{Light, LightSample, PointLight},
         material::Material,
         matrix::Matrix,
+        pattern::stripe::Stripe,
         point,
         ray::{intersection::Intersection, Ray},
         tuple::Tuple,
         vector,
     };
 
-    use super::World;
+    use super::{World, DEFAULT_MAX_DEPTH};
 
     #[test]
     fn creating_a_world() {
@@ -211,8 +294,8 @@ mod tests {
         let r = Ray::new(point!(0, 0, -5), vector!(0, 0, 1));
         let shape = *w.get_objects().first().unwrap();
         let i = Intersection::new(4.0, shape);
-        let comps = i.prepare_computations(&r);
-        let c = w.shade_hit(comps);
+        let comps = i.prepare_computations(&r, &[i]);
+        let c = w.shade_hit(comps, DEFAULT_MAX_DEPTH);
         assert_eq!(c, Color::new(0.38066, 0.47583, 0.2855))
     }
 
@@ -226,11 +309,9 @@ mod tests {
         let r = Ray::new(Tuple::point_origin(), vector!(0, 0, 1));
         let shape = *w.get_objects().last().unwrap();
         let i = Intersection::new(0.5, shape);
-        let comps = i.prepare_computations(&r);
-        let c = w.shade_hit(comps);
-        // changed assert; should be shadowed when inside
-        // original: assert_eq!(c, Color::new(0.90498, 0.90498, 0.90498))
-        assert_eq!(c, Color::new(0.1, 0.1, 0.1))
+        let comps = i.prepare_computations(&r, &[i]);
+        let c = w.shade_hit(comps, DEFAULT_MAX_DEPTH);
+        assert_eq!(c, Color::new(0.90498, 0.90498, 0.90498))
     }
 
     #[test]
@@ -273,6 +354,8 @@ mod tests {
                         .transform(Matrix::scaling_matrix(0.5, 0.5, 0.5)),
                 ),
             ],
+            bvh: OnceLock::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
         };
         let r = Ray::new(point!(0, 0, 0.75), vector!(0, 0, -1));
         // FIXME
@@ -283,28 +366,46 @@ mod tests {
     fn no_shadow_when_noting_collinear_with_point_and_light() {
         let w = World::default();
         let p = point!(0, 10, 0);
-        assert!(!w.is_shadowed(p))
+        assert!(!w.is_shadowed(p, &LightSample::between(point!(-10, 10, -10), p)))
     }
 
     #[test]
     fn shadow_when_object_is_between_point_and_light() {
         let w = World::default();
         let p = point!(10, -10, 10);
-        assert!(w.is_shadowed(p))
+        assert!(w.is_shadowed(p, &LightSample::between(point!(-10, 10, -10), p)))
     }
 
     #[test]
     fn no_shadow_when_object_behind_light() {
         let w = World::default();
         let p = point!(-20, 20, -20);
-        assert!(!w.is_shadowed(p))
+        assert!(!w.is_shadowed(p, &LightSample::between(point!(-10, 10, -10), p)))
     }
 
     #[test]
     fn no_shadow_when_object_behind_point() {
         let w = World::default();
         let p = point!(-2, 2, -2);
-        assert!(!w.is_shadowed(p))
+        assert!(!w.is_shadowed(p, &LightSample::between(point!(-10, 10, -10), p)))
+    }
+
+    #[test]
+    fn is_shadowed_tests_for_occlusion_between_two_points() {
+        let w = World::default();
+        let light_position = point!(-10, -10, -10);
+        let cases = [
+            (point!(-10, -10, 10), false),
+            (point!(10, 10, 10), true),
+            (point!(-20, -20, -20), false),
+            (point!(-5, -5, -5), false),
+        ];
+        for (point, result) in cases {
+            assert_eq!(
+                w.is_shadowed(point, &LightSample::between(light_position, point)),
+                result
+            )
+        }
     }
 
     #[test]
@@ -314,13 +415,350 @@ mod tests {
         let mut w = World::new();
         let w = w
             .add_object(s1)
-            .add_object(s2)
+            .add_object(s2.clone())
             .add_light(PointLight::new(point!(0, 0, -10), Color::white()));
         let r = Ray::new(point!(0, 0, 5), vector!(0, 0, 1));
         let i = Intersection::new(4.0, &s2);
 
-        let comps = i.prepare_computations(&r);
-        let c = w.shade_hit(comps);
+        let comps = i.prepare_computations(&r, &[i]);
+        let c = w.shade_hit(comps, DEFAULT_MAX_DEPTH);
         assert_eq!(c, Color::new(0.1, 0.1, 0.1))
     }
+
+    #[test]
+    fn second_light_illuminates_point_shadowed_from_first() {
+        let s1 = Sphere::new();
+        let s2 = Sphere::new().transform(Matrix::translation_matrix(0.0, 0.0, 10.0));
+        let lights = [
+            PointLight::new(point!(0, 0, -10), Color::white()),
+            PointLight::new(point!(0, 0, 5), Color::white()),
+        ];
+        let r = Ray::new(point!(0, 0, 5), vector!(0, 0, 1));
+        let i = Intersection::new(4.0, &s2);
+
+        // order of the lights does not matter
+        for order in [[0, 1], [1, 0]] {
+            let mut w = World::new();
+            w.add_object(s1.clone()).add_object(s2.clone());
+            for index in order {
+                let light = &lights[index];
+                w.add_light(PointLight::new(
+                    light.get_position().unwrap(),
+                    light.get_intensity(),
+                ));
+            }
+            let comps = i.prepare_computations(&r, &[i]);
+            // ambient of the first light plus full shading of the second
+            let c = w.shade_hit(comps, DEFAULT_MAX_DEPTH);
+            assert_eq!(c, Color::new(2.0, 2.0, 2.0))
+        }
+    }
+
+    #[test]
+    fn shadows_are_evaluated_per_light() {
+        let mut w = World::default();
+        w.add_light(PointLight::new(point!(20, -10, 10), Color::white()));
+        let p = point!(10, -10, 10);
+        let lights = w.get_lights();
+        assert_eq!(w.intensity_at(lights[0], p), 0.0);
+        assert_eq!(w.intensity_at(lights[1], p), 1.0)
+    }
+
+    #[test]
+    fn three_point_lighting_adds_up_contributions_of_each_light() {
+        let lights = [
+            // key, fill and back light, the latter shadowed by the sphere itself
+            (point!(-10, 10, -10), Color::white()),
+            (point!(10, 0, -10), Color::new(0.5, 0.5, 0.5)),
+            (point!(0, 5, 10), Color::new(0.8, 0.8, 0.8)),
+        ];
+        let r = Ray::new(point!(0, 0, -5), vector!(0, 0, 1));
+        let shade = |lights: &[(Tuple, Color)]| {
+            let mut w = World::default();
+            w.remove_light(0);
+            for (position, intensity) in lights {
+                w.add_light(PointLight::new(*position, *intensity));
+            }
+            let shape = w.get_objects()[0];
+            let i = Intersection::new(4.0, shape);
+            w.shade_hit(i.prepare_computations(&r, &[i]), DEFAULT_MAX_DEPTH)
+        };
+
+        let separately = lights
+            .iter()
+            .fold(Color::black(), |sum, light| sum + shade(&[*light]));
+        assert_eq!(shade(&lights), separately);
+        assert_ne!(shade(&lights), shade(&lights[..1]))
+    }
+
+    #[test]
+    fn intersecting_world_matches_testing_every_object() {
+        let mut w = World::new();
+        w.add_object(Plane::new().transform(Matrix::translation_matrix(0.0, -1.0, 0.0)));
+        for i in 0..50 {
+            let x = (i % 10) as f64 * 1.5 - 7.0;
+            let z = (i / 10) as f64 * 1.5;
+            if i % 2 == 0 {
+                w.add_object(Sphere::new().transform(Matrix::translation_matrix(x, 0.0, z)));
+            } else {
+                w.add_object(
+                    Group::new()
+                        .add_child(Cube::new().transform(Matrix::scaling_matrix(0.5, 0.5, 0.5)))
+                        .transform(Matrix::translation_matrix(x, 0.0, z)),
+                );
+            }
+        }
+
+        let rays = [
+            Ray::new(point!(0, 0, -5), vector!(0, 0, 1)),
+            Ray::new(point!(-10, 0, 1.5), vector!(1, 0, 0)),
+            Ray::new(point!(0, 5, -5), vector!(0.1, -0.5, 1).normalize()),
+            Ray::new(point!(0, 5, -5), vector!(0, 1, 0)),
+        ];
+        for r in rays {
+            let xs = w.intersect(&r);
+            let mut expected: Vec<Intersection> = w
+                .get_objects()
+                .into_iter()
+                .flat_map(|object| object.intersect(&r))
+                .collect();
+            expected.sort_by(|a, b| a.get_t().total_cmp(&b.get_t()));
+
+            assert_eq!(xs.len(), expected.len());
+            for (x, e) in xs.iter().zip(expected.iter()) {
+                assert_eq!(x.get_t(), e.get_t());
+                assert_eq!(x.get_object().get_id(), e.get_object().get_id())
+            }
+        }
+    }
+
+    #[test]
+    fn reflected_color_for_nonreflective_material() {
+        let mut w = World::new();
+        w.add_light(PointLight::new(point!(-10, 10, -10), Color::white()))
+            .add_object(Sphere::new().set_material(Material::default().set_ambient(1.0)));
+        let r = Ray::new(Tuple::point_origin(), vector!(0, 0, 1));
+        let shape = *w.get_objects().first().unwrap();
+        let i = Intersection::new(1.0, shape);
+        let comps = i.prepare_computations(&r, &[i]);
+        assert_eq!(w.reflected_color(&comps, DEFAULT_MAX_DEPTH), Color::black())
+    }
+
+    #[test]
+    fn reflected_color_for_reflective_material() {
+        let mut w = World::default();
+        w.add_object(
+            Plane::new()
+                .set_material(Material::default().set_reflective(0.5))
+                .transform(Matrix::translation_matrix(0.0, -1.0, 0.0)),
+        );
+        let r = Ray::new(
+            point!(0, 0, -3),
+            vector!(0, -2f64.sqrt() / 2.0, 2f64.sqrt() / 2.0),
+        );
+        let shape = *w.get_objects().last().unwrap();
+        let i = Intersection::new(2f64.sqrt(), shape);
+        let comps = i.prepare_computations(&r, &[i]);
+        assert_eq!(
+            w.reflected_color(&comps, DEFAULT_MAX_DEPTH),
+            Color::new(0.19033, 0.23792, 0.14275)
+        );
+        assert_eq!(
+            w.shade_hit(comps, DEFAULT_MAX_DEPTH),
+            Color::new(0.87676, 0.92434, 0.82917)
+        )
+    }
+
+    #[test]
+    fn reflected_color_at_maximum_recursive_depth() {
+        let mut w = World::default();
+        w.add_object(
+            Plane::new()
+                .set_material(Material::default().set_reflective(0.5))
+                .transform(Matrix::translation_matrix(0.0, -1.0, 0.0)),
+        );
+        let r = Ray::new(
+            point!(0, 0, -3),
+            vector!(0, -2f64.sqrt() / 2.0, 2f64.sqrt() / 2.0),
+        );
+        let shape = *w.get_objects().last().unwrap();
+        let i = Intersection::new(2f64.sqrt(), shape);
+        let comps = i.prepare_computations(&r, &[i]);
+        assert_eq!(w.reflected_color(&comps, 0), Color::black())
+    }
+
+    #[test]
+    fn color_at_with_mutually_reflective_surfaces() {
+        let mirror = Material::default().set_reflective(1.0);
+        let mut w = World::new();
+        w.add_light(PointLight::new(Tuple::point_origin(), Color::white()))
+            .add_object(
+                Plane::new()
+                    .set_material(mirror.clone())
+                    .transform(Matrix::translation_matrix(0.0, -1.0, 0.0)),
+            )
+            .add_object(
+                Plane::new()
+                    .set_material(mirror)
+                    .transform(Matrix::translation_matrix(0.0, 1.0, 0.0)),
+            );
+        // terminates thanks to the depth limit
+        let c = w.color_at(Ray::new(Tuple::point_origin(), vector!(0, 1, 0)));
+        assert_ne!(c, Color::black())
+    }
+
+    #[test]
+    fn max_depth_is_configurable() {
+        let mut w = World::new();
+        assert_eq!(w.get_max_depth(), DEFAULT_MAX_DEPTH);
+        w.set_max_depth(2);
+        assert_eq!(w.get_max_depth(), 2)
+    }
+
+    #[test]
+    fn refracted_color_with_opaque_surface() {
+        let w = World::default();
+        let shape = *w.get_objects().first().unwrap();
+        let r = Ray::new(point!(0, 0, -5), vector!(0, 0, 1));
+        let xs = [Intersection::new(4.0, shape), Intersection::new(6.0, shape)];
+        let comps = xs[0].prepare_computations(&r, &xs);
+        assert_eq!(w.refracted_color(&comps, DEFAULT_MAX_DEPTH), Color::black())
+    }
+
+    #[test]
+    fn refracted_color_at_maximum_recursive_depth() {
+        let mut w = World::new();
+        w.add_light(PointLight::new(point!(-10, 10, -10), Color::white()))
+            .add_object(
+                Sphere::new().set_material(
+                    Material::default()
+                        .set_transparency(1.0)
+                        .set_refractive_index(1.5),
+                ),
+            );
+        let shape = *w.get_objects().first().unwrap();
+        let r = Ray::new(point!(0, 0, -5), vector!(0, 0, 1));
+        let xs = [Intersection::new(4.0, shape), Intersection::new(6.0, shape)];
+        let comps = xs[0].prepare_computations(&r, &xs);
+        assert_eq!(w.refracted_color(&comps, 0), Color::black())
+    }
+
+    #[test]
+    fn refracted_color_under_total_internal_reflection() {
+        let mut w = World::new();
+        w.add_light(PointLight::new(point!(-10, 10, -10), Color::white()))
+            .add_object(
+                Sphere::new().set_material(
+                    Material::default()
+                        .set_transparency(1.0)
+                        .set_refractive_index(1.5),
+                ),
+            );
+        let shape = *w.get_objects().first().unwrap();
+        let r = Ray::new(point!(0, 0, 2f64.sqrt() / 2.0), vector!(0, 1, 0));
+        let xs = [
+            Intersection::new(-2f64.sqrt() / 2.0, shape),
+            Intersection::new(2f64.sqrt() / 2.0, shape),
+        ];
+        // inside the sphere, so the second intersection is the one to look at
+        let comps = xs[1].prepare_computations(&r, &xs);
+        assert_eq!(w.refracted_color(&comps, DEFAULT_MAX_DEPTH), Color::black())
+    }
+
+    #[test]
+    fn shade_hit_with_transparent_material() {
+        let mut w = World::default();
+        w.add_object(
+            Plane::new()
+                .set_material(
+                    Material::default()
+                        .set_transparency(0.5)
+                        .set_refractive_index(1.5),
+                )
+                .transform(Matrix::translation_matrix(0.0, -1.0, 0.0)),
+        )
+        .add_object(
+            Sphere::new()
+                .set_material(
+                    Material::default()
+                        .set_color(Color::new(1.0, 0.0, 0.0))
+                        .set_ambient(0.5),
+                )
+                .transform(Matrix::translation_matrix(0.0, -3.5, -0.5)),
+        );
+        let floor = w.get_objects()[2];
+        let r = Ray::new(
+            point!(0, 0, -3),
+            vector!(0, -2f64.sqrt() / 2.0, 2f64.sqrt() / 2.0),
+        );
+        let xs = [Intersection::new(2f64.sqrt(), floor)];
+        let comps = xs[0].prepare_computations(&r, &xs);
+        assert_eq!(
+            w.shade_hit(comps, DEFAULT_MAX_DEPTH),
+            Color::new(0.93642, 0.68642, 0.68642)
+        )
+    }
+
+    #[test]
+    fn shade_hit_with_reflective_transparent_material() {
+        let mut w = World::default();
+        w.add_object(
+            Plane::new()
+                .set_material(
+                    Material::default()
+                        .set_reflective(0.5)
+                        .set_transparency(0.5)
+                        .set_refractive_index(1.5),
+                )
+                .transform(Matrix::translation_matrix(0.0, -1.0, 0.0)),
+        )
+        .add_object(
+            Sphere::new()
+                .set_material(
+                    Material::default()
+                        .set_color(Color::new(1.0, 0.0, 0.0))
+                        .set_ambient(0.5),
+                )
+                .transform(Matrix::translation_matrix(0.0, -3.5, -0.5)),
+        );
+        let floor = w.get_objects()[2];
+        let r = Ray::new(
+            point!(0, 0, -3),
+            vector!(0, -2f64.sqrt() / 2.0, 2f64.sqrt() / 2.0),
+        );
+        let xs = [Intersection::new(2f64.sqrt(), floor)];
+        let comps = xs[0].prepare_computations(&r, &xs);
+        assert_eq!(
+            w.shade_hit(comps, DEFAULT_MAX_DEPTH),
+            Color::new(0.93391, 0.69643, 0.69243)
+        )
+    }
+
+    #[test]
+    fn pattern_is_sampled_in_object_space() {
+        let mut w = World::new();
+        w.add_light(PointLight::new(point!(0, 0, -10), Color::white()))
+            .add_object(
+                Group::new()
+                    .add_child(
+                        Sphere::new()
+                            .set_material(
+                                Material::default()
+                                    .set_pattern(Stripe::new(Color::white(), Color::black()))
+                                    .set_ambient(1.0)
+                                    .set_diffuse(0.0)
+                                    .set_specular(0.0),
+                            )
+                            .transform(Matrix::scaling_matrix(2.0, 2.0, 2.0)),
+                    )
+                    .transform(Matrix::translation_matrix(1.0, 0.0, 0.0)),
+            );
+
+        // world x 2.5 is x 0.75 in object space of the sphere
+        let c = w.color_at(Ray::new(point!(2.5, 0, -5), vector!(0, 0, 1)));
+        assert_eq!(c, Color::white());
+        // world x 0.5 is x -0.25 in object space of the sphere
+        let c = w.color_at(Ray::new(point!(0.5, 0, -5), vector!(0, 0, 1)));
+        assert_eq!(c, Color::black())
+    }
 }